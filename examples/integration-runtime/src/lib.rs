@@ -0,0 +1,147 @@
+//! # Multisig Integration Runtime
+//!
+//! A minimal runtime wiring the multisig pallet together with `pallet-balances`,
+//! `pallet-preimage` and `pallet-scheduler` through the real `construct_runtime`. The integration
+//! tests under `tests/` drive the full create, fund, propose, vote, execute and delete flow
+//! against it, and double as a reference for adopters wiring the pallet into their own runtime.
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstBool, ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot};
+use sp_runtime::{
+	traits::{IdentifyAccount, IdentityLookup, Verify},
+	BuildStorage, MultiSignature,
+};
+
+pub type Signature = MultiSignature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+pub type Balance = u128;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+/// The balance every account passed to [`new_test_ext`] starts with.
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+pub const MULTISIG_DEPOSIT: Balance = 10;
+pub const MIN_FUNDING_AMOUNT: Balance = 100;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Multisig: pallet_multisig,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type Consideration = ();
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<16>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type Preimages = Preimage;
+}
+
+parameter_types! {
+	pub const MultisigPalletId: PalletId = PalletId(*b"py/mltsg");
+	pub const DefaultExpirationBlocks: BlockNumberFor<Runtime> = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type NativeBalance = Balances;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeTask = RuntimeTask;
+	type MaxMembers = ConstU32<10>;
+	type DefaultThreshold = ConstU32<2>;
+	type DefaultExpirationBlocks = DefaultExpirationBlocks;
+	type MultisigDeposit = ConstU128<MULTISIG_DEPOSIT>;
+	type MaxMultisigsPerCreator = ConstU32<10>;
+	type MinFundingAmount = ConstU128<MIN_FUNDING_AMOUNT>;
+	type PalletId = MultisigPalletId;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	type VoteWeightProvider = ();
+	type MaxEarmarkPurposeLength = ConstU32<32>;
+	type MaxAttachments = ConstU32<8>;
+	type MaxSignalLength = ConstU32<256>;
+	type AggregateVoteEvents = ConstBool<false>;
+	type MaxAggregatedTransactions = ConstU32<64>;
+	type MaxHistoryLength = ConstU32<100>;
+	type MaxBatchCreate = ConstU32<10>;
+	type RevealPeriod = ConstU64<10>;
+	type MaxLabelLength = ConstU32<32>;
+	type DefaultMaxPendingTransactions = ConstU32<16>;
+	type MaxPendingTransactionsLimit = ConstU32<64>;
+	type PendingTransactionDeposit = ConstU128<10>;
+	type MaxContributors = ConstU32<100>;
+	type MaxEarmarks = ConstU32<16>;
+	type WeightInfo = ();
+}
+
+/// A deterministic account for tests, derived from a single byte.
+pub fn account(seed: u8) -> AccountId {
+	AccountId::new([seed; 32])
+}
+
+/// Externalities where each of the given accounts starts with [`INITIAL_BALANCE`], advanced past
+/// genesis so events get deposited.
+pub fn new_test_ext(endowed: &[AccountId]) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: endowed.iter().cloned().map(|who| (who, INITIAL_BALANCE)).collect(),
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Runs the `on_finalize` and `on_initialize` hooks of every pallet until block `n`.
+pub fn run_to_block(n: BlockNumberFor<Runtime>) {
+	use frame_support::traits::{OnFinalize, OnInitialize};
+	while System::block_number() < n {
+		AllPalletsWithSystem::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		AllPalletsWithSystem::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeSet;
+
+use frame_support::{
+	assert_ok,
+	traits::{
+		schedule::{v3::Anon, DispatchTime},
+		ConstU32, StorePreimage,
+	},
+	BoundedBTreeSet,
+};
+use pallet_multisig::{
+	Event as MultisigEvent, Members, Multisigs, TransactionStatus, Transactions, Vote,
+};
+use pallet_multisig_example::*;
+use parity_scale_codec::Encode;
+use sp_core::blake2_256;
+
+fn members(accounts: &[AccountId]) -> BoundedBTreeSet<AccountId, ConstU32<10>> {
+	BoundedBTreeSet::try_from(accounts.iter().cloned().collect::<BTreeSet<_>>())
+		.expect("Should have a valid members set")
+}
+
+/// Proposes `call` from `proposer` and returns the transaction id and call hash.
+fn propose(
+	proposer: &AccountId,
+	multisig_id: &AccountId,
+	call: RuntimeCall,
+) -> (<Runtime as frame_system::Config>::Hash, [u8; 32]) {
+	let call_hash = blake2_256(&call.encode());
+	assert_ok!(Multisig::propose_transaction(
+		RuntimeOrigin::signed(proposer.clone()),
+		multisig_id.clone(),
+		Box::new(call)
+	));
+	let transaction_id = Multisig::generate_transaction_id(
+		multisig_id.clone(),
+		proposer.clone(),
+		System::block_number(),
+		call_hash,
+	);
+	(transaction_id, call_hash)
+}
+
+#[test]
+fn full_lifecycle_works() {
+	let (alice, bob, charlie, dave) = (account(1), account(2), account(3), account(4));
+	new_test_ext(&[alice.clone(), bob.clone(), charlie.clone()]).execute_with(|| {
+		// Create
+		let multisig_id = Multisig::next_multisig_id();
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(alice.clone()),
+			members(&[alice.clone(), bob.clone(), charlie.clone()]),
+			Some(2)
+		));
+		assert!(Multisigs::<Runtime>::contains_key(&multisig_id));
+		assert!(Members::<Runtime>::contains_key(&multisig_id, &bob));
+
+		// Fund
+		assert_ok!(Multisig::fund_multisig(
+			RuntimeOrigin::signed(bob.clone()),
+			multisig_id.clone(),
+			10_000
+		));
+		// Creation already left the multisig with one existential deposit
+		assert_eq!(Balances::free_balance(&multisig_id), 10_001);
+
+		// Propose
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: dave.clone(),
+			value: 1_000,
+		});
+		let (transaction_id, call_hash) = propose(&alice, &multisig_id, transfer);
+
+		// Vote
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(charlie.clone()),
+			multisig_id.clone(),
+			transaction_id,
+			Vote::Approve
+		));
+
+		// Execute
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(bob.clone()),
+			multisig_id.clone(),
+			transaction_id,
+			call_hash
+		));
+		// The transfer is paid by the multisig, not by the proposer or the submitter
+		assert_eq!(Balances::free_balance(&dave), 1_000);
+		assert_eq!(Balances::free_balance(&multisig_id), 9_001);
+		assert!(Transactions::<Runtime>::get(&multisig_id, &transaction_id).is_none());
+
+		// Delete
+		let delete = RuntimeCall::Multisig(pallet_multisig::Call::delete_multisig {
+			multisig_id: multisig_id.clone(),
+		});
+		let (transaction_id, call_hash) = propose(&bob, &multisig_id, delete);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			multisig_id.clone(),
+			transaction_id,
+			Vote::Approve
+		));
+		let creator_balance = Balances::free_balance(&alice);
+		let multisig_balance = Balances::total_balance(&multisig_id);
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(alice.clone()),
+			multisig_id.clone(),
+			transaction_id,
+			call_hash
+		));
+		System::assert_has_event(
+			MultisigEvent::MultisigDeleted {
+				from: multisig_id.clone(),
+				multisig: multisig_id.clone(),
+			}
+			.into(),
+		);
+		assert!(!Multisigs::<Runtime>::contains_key(&multisig_id));
+		assert_eq!(Members::<Runtime>::iter_prefix(&multisig_id).count(), 0);
+		// The remaining funds and the creation deposit are swept to the creator
+		assert_eq!(Balances::free_balance(&alice), creator_balance + multisig_balance);
+		assert_eq!(Balances::total_balance(&multisig_id), 0);
+	});
+}
+
+#[test]
+fn scheduled_execution_works() {
+	let (alice, bob, dave) = (account(1), account(2), account(4));
+	new_test_ext(&[alice.clone(), bob.clone()]).execute_with(|| {
+		let multisig_id = Multisig::next_multisig_id();
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(alice.clone()),
+			members(&[alice.clone(), bob.clone()]),
+			Some(2)
+		));
+		assert_ok!(Multisig::fund_multisig(
+			RuntimeOrigin::signed(bob.clone()),
+			multisig_id.clone(),
+			10_000
+		));
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: dave.clone(),
+			value: 1_000,
+		});
+		let (transaction_id, call_hash) = propose(&alice, &multisig_id, transfer);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(bob.clone()),
+			multisig_id.clone(),
+			transaction_id,
+			Vote::Approve
+		));
+
+		// Schedule the submission on behalf of a member, noting the call as a preimage
+		let submit = RuntimeCall::Multisig(pallet_multisig::Call::submit_transaction {
+			multisig_id: multisig_id.clone(),
+			transaction_id,
+			call_hash,
+		});
+		let bounded = Preimage::bound(submit).expect("Call should be bounded");
+		assert_ok!(<Scheduler as Anon<_, _, _>>::schedule(
+			DispatchTime::At(10),
+			None,
+			0,
+			frame_system::RawOrigin::Signed(alice.clone()).into(),
+			bounded,
+		));
+
+		run_to_block(9);
+		assert_eq!(Balances::free_balance(&dave), 0);
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(&dave), 1_000);
+		System::assert_has_event(
+			MultisigEvent::TransactionExecuted {
+				submitter: alice.clone(),
+				transaction: transaction_id,
+				multisig: multisig_id.clone(),
+				approvals: 2,
+				rejections: 0,
+				status: TransactionStatus::Complete,
+				call_hash,
+				approved_at: 1,
+			}
+			.into(),
+		);
+	});
+}
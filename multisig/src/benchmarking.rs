@@ -4,31 +4,200 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Multisig;
+use codec::Encode;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{
+	traits::fungible::{Inspect, Mutate},
+	BoundedBTreeSet,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_core::blake2_256;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError,
+};
+use sp_std::prelude::*;
 
-#[benchmarks]
+/// Create a funded multisig of the whitelisted caller and `m - 1` other members, returning the
+/// caller and the multisig along with its members.
+fn create_multisig<T: Config>(
+	m: u32,
+	threshold: u32,
+) -> Result<(T::AccountId, T::AccountId, Vec<T::AccountId>), BenchmarkError> {
+	let caller: T::AccountId = whitelisted_caller();
+	let funds = T::NativeBalance::minimum_balance()
+		.saturating_add(T::MultisigDeposit::get())
+		.saturating_add(T::MinFundingAmount::get())
+		.saturating_mul(100u32.into());
+	T::NativeBalance::set_balance(&caller, funds);
+	let mut members = BoundedBTreeSet::new();
+	members
+		.try_insert(caller.clone())
+		.map_err(|_| BenchmarkError::Stop("members"))?;
+	for i in 1..m {
+		members
+			.try_insert(account("member", i, 0))
+			.map_err(|_| BenchmarkError::Stop("members"))?;
+	}
+	let multisig_id =
+		Multisig::<T>::do_create_multisig(caller.clone(), members.clone(), Some(threshold))?;
+	Ok((caller, multisig_id, members.into_iter().collect()))
+}
+
+/// A call cheap to dispatch, distinct for every `i`.
+fn remark<T: Config>(i: u32) -> <T as Config>::RuntimeCall
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	frame_system::Call::<T>::remark { remark: i.encode() }.into()
+}
+
+#[benchmarks(where <T as Config>::RuntimeCall: From<frame_system::Call<T>>)]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn propose_transaction(m: Linear<1, { T::MaxMembers::get() }>) -> Result<(), BenchmarkError> {
+		let (caller, multisig_id, _) = create_multisig::<T>(m, m)?;
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		_(RawOrigin::Signed(caller), multisig_id.clone(), Box::new(remark::<T>(0)));
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(PendingTransactionCount::<T>::get(&multisig_id), 1);
+		Ok(())
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn vote(m: Linear<2, { T::MaxMembers::get() }>) -> Result<(), BenchmarkError> {
+		let (caller, multisig_id, members) = create_multisig::<T>(m, m)?;
+		let call = remark::<T>(0);
+		let call_hash = blake2_256(&call.encode());
+		Multisig::<T>::propose_transaction(
+			RawOrigin::Signed(caller.clone()).into(),
+			multisig_id.clone(),
+			Box::new(call),
+		)?;
+		let transaction_id = Multisig::<T>::generate_transaction_id(
+			multisig_id.clone(),
+			caller.clone(),
+			frame_system::Pallet::<T>::block_number(),
+			call_hash,
+		);
+		// Every other member votes first, so the last vote reaches the threshold
+		let others: Vec<_> = members.into_iter().filter(|member| *member != caller).collect();
+		let (voter, others) = others.split_last().ok_or(BenchmarkError::Stop("members"))?;
+		for member in others {
+			Multisig::<T>::do_vote(
+				member.clone(),
+				multisig_id.clone(),
+				transaction_id,
+				Vote::Approve,
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(voter.clone()), multisig_id.clone(), transaction_id, Vote::Approve);
+
+		let transaction =
+			Transactions::<T>::get(&multisig_id, &transaction_id).ok_or("transaction")?;
+		assert_eq!(transaction.votes.len() as u32, m);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn submit_transaction(m: Linear<1, { T::MaxMembers::get() }>) -> Result<(), BenchmarkError> {
+		let (caller, multisig_id, members) = create_multisig::<T>(m, m)?;
+		let call = remark::<T>(0);
+		let call_hash = blake2_256(&call.encode());
+		Multisig::<T>::propose_transaction(
+			RawOrigin::Signed(caller.clone()).into(),
+			multisig_id.clone(),
+			Box::new(call),
+		)?;
+		let transaction_id = Multisig::<T>::generate_transaction_id(
+			multisig_id.clone(),
+			caller.clone(),
+			frame_system::Pallet::<T>::block_number(),
+			call_hash,
+		);
+		// Every member approves, the proposer on proposal
+		for member in members.iter().filter(|member| **member != caller) {
+			Multisig::<T>::do_vote(
+				member.clone(),
+				multisig_id.clone(),
+				transaction_id,
+				Vote::Approve,
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig_id.clone(), transaction_id, call_hash);
+
+		assert!(!Transactions::<T>::contains_key(&multisig_id, &transaction_id));
+		assert_eq!(PendingTransactionCount::<T>::get(&multisig_id), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delete_multisig(
+		p: Linear<0, { T::MaxPendingTransactionsLimit::get() }>,
+		m: Linear<1, { T::MaxMembers::get() }>,
+		c: Linear<0, { T::MaxContributors::get() }>,
+		e: Linear<0, { T::MaxEarmarks::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let amount = T::MinFundingAmount::get().max(T::NativeBalance::minimum_balance());
+		let (caller, multisig_id, members) = create_multisig::<T>(m, 1)?;
+		// Every member keeps an entry in each of the member maps cleared on deletion
+		for member in members.iter() {
+			VoteDelegates::<T>::insert(&multisig_id, member, caller.clone());
+			LastSeen::<T>::insert(&multisig_id, member, BlockNumberFor::<T>::zero());
+			MemberLabels::<T>::insert(&multisig_id, member, MemberLabel::<T>::default());
+		}
+		MaxPendingTransactions::<T>::insert(&multisig_id, p);
+		// Every pending transaction holds a call to clear on deletion
+		for i in 0..p {
+			let call = remark::<T>(i);
+			let call_hash = blake2_256(&call.encode());
+			Multisig::<T>::build_transaction(
+				caller.clone(),
+				multisig_id.clone(),
+				Some(Box::new(call)),
+				call_hash,
+				Threshold::Absolute(1),
+				1,
+				Default::default(),
+			)
+			.map_err(DispatchError::from)?;
+		}
+		assert_eq!(PendingTransactionCount::<T>::get(&multisig_id), p);
+		// Every contribution is refunded on deletion
+		for i in 0..c {
+			let contributor: T::AccountId = account("contributor", i, 0);
+			T::NativeBalance::set_balance(&contributor, amount.saturating_mul(2u32.into()));
+			Multisig::<T>::do_contribute(contributor, multisig_id.clone(), amount)?;
+		}
+		// Every earmark is released on deletion
+		T::NativeBalance::mint_into(&multisig_id, amount.saturating_mul(e.into()))?;
+		for i in 0..e {
+			let purpose = EarmarkPurpose::<T>::try_from(i.encode())
+				.map_err(|_| BenchmarkError::Stop("purpose"))?;
+			Multisig::<T>::earmark_funds(
+				RawOrigin::Signed(multisig_id.clone()).into(),
+				multisig_id.clone(),
+				purpose,
+				amount,
+			)?;
+		}
+
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		_(RawOrigin::Signed(multisig_id.clone()), multisig_id.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!Multisigs::<T>::contains_key(&multisig_id));
+		assert_eq!(Transactions::<T>::iter_prefix(&multisig_id).count(), 0);
+		assert_eq!(Contributions::<T>::iter_prefix(&multisig_id).count(), 0);
+		assert_eq!(Earmarks::<T>::iter_prefix(&multisig_id).count(), 0);
+		assert!(T::NativeBalance::balance(&caller) > Zero::zero());
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Multisig, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,84 +1,833 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Decode;
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage::with_storage_layer,
+	traits::{
+		fungible::{hold::Mutate as HoldMutate, Inspect, InspectHold, Mutate},
+		tokens::{Fortitude, Precision, Preservation},
+	},
+};
 use frame_system::pallet_prelude::*;
 use sp_core::blake2_256;
 use sp_runtime::{
-	traits::{Saturating, TrailingZeroInput},
-	BoundedBTreeMap,
+	traits::{Saturating, TrailingZeroInput, Zero},
+	BoundedBTreeMap, BoundedBTreeSet,
 };
 use sp_std::prelude::*;
 
 use super::*;
 
 impl<T: Config> Pallet<T> {
+	/// Derive the account the transactions of a multisig proposed under `category` are
+	/// dispatched from, so downstream pallets can grant permissions to a single category of a
+	/// multisig rather than to the whole multisig.
+	pub fn category_account_id(
+		multisig_id: &T::AccountId,
+		category: &ProposalCategory,
+	) -> T::AccountId {
+		let entropy = (b"pba/category", multisig_id, category).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
 	/// Derive a unique account id for the multisig.
 	pub fn generate_multi_account_id(nonce: u64) -> T::AccountId {
 		let entropy = (b"pba/multisig", nonce).using_encoded(blake2_256);
 		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
 			.expect("infinite length input; no invalid inputs for type; qed")
 	}
+	/// The account id the next created multisig will be derived to, letting it be known before
+	/// creation.
+	pub fn next_multisig_id() -> T::AccountId {
+		Self::generate_multi_account_id(MultisigNonce::<T>::get())
+	}
+	/// The multisigs created by the account which still exist.
+	pub fn multisigs_by_creator(creator: &T::AccountId) -> Vec<T::AccountId> {
+		MultisigsByCreator::<T>::get(creator).into_inner()
+	}
+	/// Ensure the origin is the multisig account itself, which it only is when the call is
+	/// dispatched by a proposal of the multisig that reached its threshold.
+	pub fn ensure_multisig_origin(
+		origin: OriginFor<T>,
+		multisig_id: &T::AccountId,
+	) -> DispatchResult {
+		let who = ensure_signed(origin)?;
+		ensure!(&who == multisig_id, DispatchError::BadOrigin);
+		ensure!(Multisigs::<T>::contains_key(multisig_id), Error::<T>::MultisigDoesNotExist);
+		Ok(())
+	}
+	/// Derive the id of a transaction proposed on a multisig. The multisig id is mixed in as a
+	/// domain tag, so the same call proposed by the same proposer in the same block on two
+	/// multisigs sharing signers never derives the same id.
 	pub fn generate_transaction_id(
+		multisig_id: T::AccountId,
 		proposer: T::AccountId,
 		block_number: BlockNumberFor<T>,
 		call_hash: [u8; 32],
 	) -> T::Hash {
-		let entropy =
-			(b"pba/transaction", proposer, block_number, call_hash).using_encoded(blake2_256);
+		let entropy = (b"pba/transaction", multisig_id, proposer, block_number, call_hash)
+			.using_encoded(blake2_256);
 		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
 			.expect("infinite length input; no invalid inputs for type; qed")
 	}
-	/// Tally the "approved" and "rejected" votes on a proposed transaction.
+	/// The funds of the multisig earmarked under each purpose.
+	pub fn earmarks(multisig_id: &T::AccountId) -> Vec<(EarmarkPurpose<T>, BalanceOf<T>)> {
+		Earmarks::<T>::iter_prefix(multisig_id).collect()
+	}
+	/// Every member of the multisig, in account order, along with their label if any.
+	pub fn members(multisig_id: &T::AccountId) -> Vec<(T::AccountId, Option<MemberLabel<T>>)> {
+		let members = Multisigs::<T>::get(multisig_id).map(|multisig| multisig.members);
+		members
+			.into_iter()
+			.flatten()
+			.map(|member| {
+				let label = MemberLabels::<T>::get(multisig_id, &member);
+				(member, label)
+			})
+			.collect()
+	}
+	/// The combined vote weight of the members, i.e. the most a transaction can be approved or
+	/// rejected with.
+	pub fn total_vote_weight(members: &BoundedBTreeSet<T::AccountId, T::MaxMembers>) -> u32 {
+		members.iter().fold(0u32, |total, member| {
+			total.saturating_add(T::VoteWeightProvider::vote_weight(member))
+		})
+	}
+	/// Every member of the multisig, in account order, along with the block of their last
+	/// heartbeat if any.
+	pub fn liveness(multisig_id: &T::AccountId) -> Vec<(T::AccountId, Option<BlockNumberFor<T>>)> {
+		let members = Multisigs::<T>::get(multisig_id).map(|multisig| multisig.members);
+		members
+			.into_iter()
+			.flatten()
+			.map(|member| {
+				let last_seen = LastSeen::<T>::get(multisig_id, &member);
+				(member, last_seen)
+			})
+			.collect()
+	}
+	/// The activity history of the multisig from `since_block` onwards, oldest first.
+	pub fn activity(
+		multisig_id: &T::AccountId,
+		since_block: BlockNumberFor<T>,
+	) -> Vec<ActivityEntryOf<T>> {
+		History::<T>::get(multisig_id)
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|entry| entry.block >= since_block)
+			.collect()
+	}
+	/// Append an activity to the history of an existing multisig, dropping the oldest entry once
+	/// the history is full.
+	pub fn record_activity(
+		multisig_id: &T::AccountId,
+		activity: Activity<T::AccountId, BalanceOf<T>, T::Hash>,
+	) {
+		let entry = ActivityEntry { block: frame_system::Pallet::<T>::block_number(), activity };
+		History::<T>::mutate_exists(multisig_id, |maybe_history| {
+			if let Some(history) = maybe_history {
+				if history.try_push(entry.clone()).is_err() && !history.is_empty() {
+					history.remove(0);
+					let _ = history.try_push(entry);
+				}
+			}
+		});
+	}
+	/// The minimum amount accepted when funding the multisig, falling back to the default.
+	pub fn min_funding_amount(multisig_id: &T::AccountId) -> BalanceOf<T> {
+		MinFundingAmounts::<T>::get(multisig_id).unwrap_or_else(T::MinFundingAmount::get)
+	}
+	/// The maximum number of transactions pending on the multisig, falling back to the default.
+	pub fn max_pending_transactions(multisig_id: &T::AccountId) -> u32 {
+		MaxPendingTransactions::<T>::get(multisig_id)
+			.unwrap_or_else(T::DefaultMaxPendingTransactions::get)
+	}
+	/// Hold or release funds of the multisig so the deposit held for its pending transaction
+	/// limit matches `limit`. Returns the deposit held afterwards.
+	pub fn adjust_pending_limit_deposit(
+		multisig_id: &T::AccountId,
+		limit: u32,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let extra = limit.saturating_sub(T::DefaultMaxPendingTransactions::get());
+		let required = T::PendingTransactionDeposit::get().saturating_mul(extra.into());
+		let reason = HoldReason::PendingLimitDeposit.into();
+		let held = T::NativeBalance::balance_on_hold(&reason, multisig_id);
+		if required > held {
+			T::NativeBalance::hold(&reason, multisig_id, required.saturating_sub(held))
+				.map_err(|_| Error::<T>::NotEnoughFunds)?;
+		} else if required < held {
+			T::NativeBalance::release(
+				&reason,
+				multisig_id,
+				held.saturating_sub(required),
+				Precision::Exact,
+			)?;
+		}
+		Ok(required)
+	}
+	/// Create a new multisig account with the creator as a member, taking the creation deposit
+	/// from the creator. Returns the derived id of the multisig.
+	pub fn do_create_multisig(
+		who: T::AccountId,
+		members: BoundedBTreeSet<T::AccountId, T::MaxMembers>,
+		threshold: Option<u32>,
+	) -> Result<T::AccountId, DispatchError> {
+		// Ensure the creator is a member of the multisig
+		ensure!(members.contains(&who), Error::<T>::ProposerMustBeMember);
+		// Ensure the threshold can be reached by the combined vote weight of the members
+		let votes_required = threshold.unwrap_or(T::DefaultThreshold::get());
+		ensure!(votes_required <= Self::total_vote_weight(&members), Error::<T>::ThresholdTooHigh);
+		// Ensure the threshold requires at least one vote
+		ensure!(votes_required > 0, Error::<T>::ThresholdTooLow);
+		// Ensure the creator has not reached their multisig limit
+		let created = MultisigsByCreator::<T>::decode_len(&who).unwrap_or(0) as u32;
+		ensure!(created < T::MaxMultisigsPerCreator::get(), Error::<T>::TooManyMultisigs);
+		let deposit = T::MultisigDeposit::get();
+		// Ensure the signer has enough balance to create the multisig
+		ensure!(
+			T::NativeBalance::reducible_balance(&who, Preservation::Preserve, Fortitude::Polite) >=
+				deposit,
+			Error::<T>::NotEnoughFunds
+		);
+		let nonce = MultisigNonce::<T>::get();
+		// Increment the multisig nonce
+		MultisigNonce::<T>::put(nonce + 1);
+		let multisig_id = Self::generate_multi_account_id(nonce);
+		// Use the passed threshold or the default
+		let threshold = Threshold::Absolute(threshold.unwrap_or(T::DefaultThreshold::get()));
+		for member in members.iter() {
+			Members::<T>::insert(&multisig_id, member, ());
+		}
+		let multisig = MultisigAccount {
+			creator: who.clone(),
+			members,
+			threshold,
+			created_at: frame_system::Pallet::<T>::block_number(),
+		};
+		Multisigs::<T>::insert(&multisig_id, multisig);
+		MultisigsByCreator::<T>::try_mutate(&who, |created| created.try_push(multisig_id.clone()))
+			.map_err(|_| Error::<T>::TooManyMultisigs)?;
+		// Transfer to multisig account add 1 to the deposit to cover the transfer fee
+		let total_deposit: BalanceOf<T> = deposit.saturating_add(1u32.into());
+		T::NativeBalance::transfer(&who, &multisig_id, total_deposit, Preservation::Expendable)?;
+		// Hold that amount in the multisig account as a "deposit"
+		T::NativeBalance::hold(&HoldReason::MultisigCreationDeposit.into(), &multisig_id, deposit)?;
+		History::<T>::insert(&multisig_id, BoundedVec::default());
+		Self::record_activity(&multisig_id, Activity::Created { creator: who.clone() });
+		Self::deposit_event(Event::NewMultisig { creator: who, multisig: multisig_id.clone() });
+		Ok(multisig_id)
+	}
+	/// Transfer funds from an account into an existing multisig account.
+	pub fn do_fund_multisig(
+		from: T::AccountId,
+		multisig_id: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		// Ensure the fund amount is not below the minimum to prevent dust spam
+		ensure!(amount >= Self::min_funding_amount(&multisig_id), Error::<T>::BelowMinimumFunding);
+		// Ensure the origin has enough balance to fund the multisig
+		ensure!(
+			T::NativeBalance::reducible_balance(&from, Preservation::Preserve, Fortitude::Polite) >=
+				amount,
+			Error::<T>::NotEnoughFunds
+		);
+		ensure!(Multisigs::<T>::contains_key(&multisig_id), Error::<T>::MultisigDoesNotExist);
+		// Transfer the funds to the multisig account
+		T::NativeBalance::transfer(&from, &multisig_id, amount, Preservation::Preserve)?;
+		Self::record_activity(&multisig_id, Activity::Funded { from: from.clone(), amount });
+		Self::deposit_event(Event::MultisigFunded { from, to: multisig_id, amount });
+		Ok(())
+	}
+	/// Funds the multisig and holds the funded amount in escrow, attributed to the contributor.
+	pub fn do_contribute(
+		from: T::AccountId,
+		multisig_id: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		// Ensure the multisig has room for a new contributor
+		if !Contributions::<T>::contains_key(&multisig_id, &from) {
+			ContributorCount::<T>::try_mutate(&multisig_id, |count| {
+				ensure!(*count < T::MaxContributors::get(), Error::<T>::TooManyContributors);
+				count.saturating_inc();
+				Ok::<_, Error<T>>(())
+			})?;
+		}
+		Self::do_fund_multisig(from.clone(), multisig_id.clone(), amount)?;
+		T::NativeBalance::hold(&HoldReason::Contribution.into(), &multisig_id, amount)?;
+		Contributions::<T>::mutate(&multisig_id, &from, |contributed| {
+			contributed.saturating_accrue(amount)
+		});
+		Self::deposit_event(Event::ContributionMade { from, multisig: multisig_id, amount });
+		Ok(())
+	}
+	/// Releases the contributions held on the multisig and refunds each contributor the amount
+	/// they contributed. Contributions stay on hold until then, so the multisig cannot spend
+	/// them.
+	pub fn refund_contributions(multisig_id: &T::AccountId) -> DispatchResult {
+		T::NativeBalance::release_all(
+			&HoldReason::Contribution.into(),
+			multisig_id,
+			Precision::BestEffort,
+		)?;
+		ContributorCount::<T>::remove(multisig_id);
+		for (contributor, contributed) in Contributions::<T>::drain_prefix(multisig_id) {
+			T::NativeBalance::transfer(
+				multisig_id,
+				&contributor,
+				contributed,
+				Preservation::Expendable,
+			)
+			.map_err(|_| Error::<T>::TransferFailed)?;
+			Self::deposit_event(Event::ContributionRefunded {
+				to: contributor,
+				multisig: multisig_id.clone(),
+				amount: contributed,
+			});
+		}
+		Ok(())
+	}
+	/// The calls of the full lifecycle of a transaction proposing `call` on the multisig: the
+	/// proposal, the votes needed on top of the proposer's own to reach the threshold and the
+	/// submission. Returns `None` if the multisig does not exist.
+	pub fn lifecycle_calls(
+		multisig_id: T::AccountId,
+		call: Box<<T as Config>::RuntimeCall>,
+	) -> Option<Vec<Call<T>>> {
+		let multisig = Multisigs::<T>::get(&multisig_id)?;
+		let call_hash = blake2_256(&call.encode());
+		// The proposer approves on proposal, so one vote less is needed from the other members.
+		// Every vote is counted as weighing one, which is the most votes the threshold can take.
+		let member_count = multisig.members.len() as u32;
+		let votes = multisig
+			.threshold
+			.votes_required(Self::total_vote_weight(&multisig.members))
+			.saturating_sub(1)
+			.min(member_count.saturating_sub(1));
+		let transaction_id = T::Hash::default();
+		let mut calls =
+			sp_std::vec![Call::<T>::propose_transaction { multisig_id: multisig_id.clone(), call }];
+		calls.extend((0..votes).map(|_| Call::<T>::vote {
+			multisig_id: multisig_id.clone(),
+			transaction_id,
+			vote: Vote::Approve,
+		}));
+		calls.push(Call::<T>::submit_transaction { multisig_id, transaction_id, call_hash });
+		Some(calls)
+	}
+	/// Record a member's vote on a pending transaction, noting when the approval threshold is
+	/// first reached.
+	pub fn do_vote(
+		who: T::AccountId,
+		multisig_id: T::AccountId,
+		transaction_id: T::Hash,
+		vote: Vote,
+	) -> DispatchResult {
+		// Ensure the voter is a member of the multisig
+		ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+		Transactions::<T>::try_mutate(
+			&multisig_id,
+			&transaction_id,
+			|maybe_transaction| -> Result<(), Error<T>> {
+				let transaction =
+					maybe_transaction.as_mut().ok_or(Error::<T>::TransactionDoesNotExist)?;
+				// Ensure the transaction has a "Pending" status
+				ensure!(
+					transaction.status == TransactionStatus::Pending,
+					Error::<T>::TransactionNotPending
+				);
+				// Ensure the transaction has not passed its expiry block
+				ensure!(
+					transaction.expires_at >= frame_system::Pallet::<T>::block_number(),
+					Error::<T>::TransactionExpired
+				);
+				// Ensure the transaction has not already been voted on by the proposer
+				ensure!(!transaction.votes.contains_key(&who), Error::<T>::AlreadyVoted);
+				// Record the vote with the current weight of the voter and update the cached tally
+				let weight = T::VoteWeightProvider::vote_weight(&who);
+				transaction
+					.votes
+					.try_insert(who.clone(), (vote.clone(), weight))
+					.map_err(|_| Error::<T>::VoteLimitReached)?;
+				match vote {
+					Vote::Approve => transaction.tally.approvals.saturating_accrue(weight),
+					Vote::Reject => transaction.tally.rejections.saturating_accrue(weight),
+				}
+				Self::record_activity(
+					&multisig_id,
+					Activity::Voted {
+						voter: who.clone(),
+						transaction: transaction_id,
+						vote: vote.clone(),
+					},
+				);
+				if !T::AggregateVoteEvents::get() ||
+					!Self::aggregate_vote(&multisig_id, &transaction_id)
+				{
+					Self::deposit_event(Event::TransactionVoted {
+						voter: who,
+						transaction: transaction_id,
+						multisig: multisig_id.clone(),
+						vote,
+						call_hash: transaction.call_hash,
+					});
+				}
+				// Record the block at which the approval threshold is first reached
+				if transaction.approved_at.is_none() {
+					let approvals = transaction.tally.approvals;
+					if approvals >= transaction.threshold.votes_required(transaction.total_weight) {
+						let now = frame_system::Pallet::<T>::block_number();
+						transaction.approved_at = Some(now);
+						Self::deposit_event(Event::ThresholdReached {
+							transaction: transaction_id,
+							multisig: multisig_id.clone(),
+							approvals,
+							approved_at: now,
+						});
+					}
+				}
+				Ok(())
+			},
+		)?;
+		Ok(())
+	}
+	/// Canonical digest of a proposed transaction that members sign off-chain to approve it.
+	///
+	/// The digest is the `blake2_256` hash of the SCALE encoded tuple
+	/// `(pallet_id, multisig_id, nonce, call_hash, expires_at)`, which binds an approval to a single
+	/// proposal of a single multisig until it expires.
+	pub fn proposal_digest(
+		multisig_id: &T::AccountId,
+		nonce: u64,
+		call_hash: [u8; 32],
+		expires_at: BlockNumberFor<T>,
+	) -> [u8; 32] {
+		(T::PalletId::get(), multisig_id, nonce, call_hash, expires_at).using_encoded(blake2_256)
+	}
+	/// Payload members sign off-chain to approve a proposal. The proposal digest is bound to the
+	/// genesis hash and spec version of this chain so that a signed approval can never be replayed
+	/// on a fork or a sister chain sharing the same member set.
+	pub fn approval_payload(digest: [u8; 32]) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		let spec_version = <T as frame_system::Config>::Version::get().spec_version;
+		(genesis_hash, spec_version, digest).encode()
+	}
+	/// Tally the "approved" and "rejected" votes on a proposal, weighted with the current vote
+	/// weight of each voter.
 	pub fn do_tally_votes(
 		status: TransactionStatus,
 		votes: BoundedBTreeMap<T::AccountId, Vote, T::MaxMembers>,
 	) -> Result<(u32, u32), Error<T>> {
 		// Ensure the transaction has a "Pending" status
 		ensure!(status == TransactionStatus::Pending, Error::<T>::TransactionNotPending);
-		// Accumulate the number of approval and rejection votes
-		let (approvals, rejections) = votes.values().fold((0, 0), |(a, r), vote| match vote {
-			Vote::Approve => (a + 1, r),
-			Vote::Reject => (a, r + 1),
+		// Accumulate the weight of approval and rejection votes
+		let (approvals, rejections) = votes.iter().fold((0u32, 0u32), |(a, r), (who, vote)| {
+			let weight = T::VoteWeightProvider::vote_weight(who);
+			match vote {
+				Vote::Approve => (a.saturating_add(weight), r),
+				Vote::Reject => (a, r.saturating_add(weight)),
+			}
 		});
 		Ok((approvals, rejections))
 	}
+	/// Tally the "approved" and "rejected" votes on a proposal, each weighted as it was when cast.
+	pub fn tally(votes: &BoundedBTreeMap<T::AccountId, (Vote, u32), T::MaxMembers>) -> Tally {
+		votes.values().fold(Tally::default(), |mut tally, (vote, weight)| {
+			match vote {
+				Vote::Approve => tally.approvals.saturating_accrue(*weight),
+				Vote::Reject => tally.rejections.saturating_accrue(*weight),
+			}
+			tally
+		})
+	}
+	/// Ensure the cached tally of every pending transaction matches the votes cast on it, each
+	/// weighted as it was when cast.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		for (_, _, transaction) in Transactions::<T>::iter() {
+			if transaction.status != TransactionStatus::Pending {
+				continue;
+			}
+			ensure!(
+				transaction.tally == Self::tally(&transaction.votes),
+				"The cached tally of a transaction does not match its votes"
+			);
+		}
+		Ok(())
+	}
+	/// Count a vote towards the `VotesRecorded` event of its transaction, returning whether it was
+	/// counted. Once votes are aggregated for `MaxAggregatedTransactions` transactions within the
+	/// block, the votes on other transactions are left to be reported individually.
+	fn aggregate_vote(multisig_id: &T::AccountId, transaction_id: &T::Hash) -> bool {
+		if !BlockVoteCounts::<T>::contains_key(multisig_id, transaction_id) {
+			let aggregated = AggregatedTransactionCount::<T>::get();
+			if aggregated >= T::MaxAggregatedTransactions::get() {
+				return false;
+			}
+			AggregatedTransactionCount::<T>::put(aggregated.saturating_add(1));
+		}
+		BlockVoteCounts::<T>::mutate(multisig_id, transaction_id, |count| count.saturating_inc());
+		true
+	}
+	/// The weight of dissolving a multisig, with as many pending transactions, members,
+	/// contributors and earmarks as it is allowed.
+	pub fn dissolve_weight(multisig_id: &T::AccountId) -> Weight {
+		T::WeightInfo::delete_multisig(
+			Self::max_pending_transactions(multisig_id),
+			T::MaxMembers::get(),
+			T::MaxContributors::get(),
+			T::MaxEarmarks::get(),
+		)
+	}
+	/// Release the funds held by a multisig, sweep them out and remove the multisig along with
+	/// everything still pending on it, returning the account the funds were swept to.
+	///
+	/// The contributions are refunded to their contributors and the rest is swept to the
+	/// `beneficiary`, or the creator without one.
+	pub fn dissolve_multisig(
+		who: &T::AccountId,
+		multisig_id: &T::AccountId,
+		beneficiary: Option<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		let multisig = Multisigs::<T>::take(multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
+		// Release all the "Hold" funds from the multisig account
+		T::NativeBalance::release_all(
+			&HoldReason::MultisigCreationDeposit.into(),
+			multisig_id,
+			Precision::BestEffort,
+		)?;
+		// Release all of the earmarked funds from the multisig account
+		EarmarkCount::<T>::remove(multisig_id);
+		for (purpose, amount) in Earmarks::<T>::drain_prefix(multisig_id) {
+			Self::deposit_event(Event::EarmarkReleased {
+				multisig: multisig_id.clone(),
+				purpose,
+				amount,
+			});
+		}
+		T::NativeBalance::release_all(
+			&HoldReason::Earmark.into(),
+			multisig_id,
+			Precision::BestEffort,
+		)?;
+		T::NativeBalance::release_all(
+			&HoldReason::PendingLimitDeposit.into(),
+			multisig_id,
+			Precision::BestEffort,
+		)?;
+		// Refund the contributions before sweeping what is left
+		Self::refund_contributions(multisig_id)?;
+		let to = beneficiary.unwrap_or_else(|| multisig.creator.clone());
+		// All funds in the multisig account to reap the account
+		let total_funds = T::NativeBalance::reducible_balance(
+			multisig_id,
+			Preservation::Expendable,
+			Fortitude::Force,
+		);
+		// Transfer the remaining funds including the deposit
+		T::NativeBalance::transfer(multisig_id, &to, total_funds, Preservation::Expendable)
+			.map_err(|_| Error::<T>::TransferFailed)?;
+		// Cancel all of the transactions still pending on the multisig
+		for (transaction_id, transaction) in Transactions::<T>::drain_prefix(multisig_id) {
+			Self::remove_call(multisig_id, &transaction_id, transaction.call_hash);
+			TransactionCategories::<T>::remove(multisig_id, &transaction_id);
+			RevealedAt::<T>::remove(multisig_id, &transaction_id);
+			Self::deposit_event(Event::TransactionCanceled {
+				submitter: who.clone(),
+				transaction: transaction_id,
+				multisig: multisig_id.clone(),
+				status: TransactionStatus::Canceled,
+				call_hash: transaction.call_hash,
+			});
+		}
+		// Cancel all of the signals still pending on the multisig
+		for (signal_id, _) in Signals::<T>::drain_prefix(multisig_id) {
+			Self::deposit_event(Event::SignalCanceled {
+				signal: signal_id,
+				multisig: multisig_id.clone(),
+			});
+		}
+		History::<T>::remove(multisig_id);
+		let _ = Members::<T>::clear_prefix(multisig_id, T::MaxMembers::get(), None);
+		let _ = VoteDelegates::<T>::clear_prefix(multisig_id, T::MaxMembers::get(), None);
+		let _ = LastSeen::<T>::clear_prefix(multisig_id, T::MaxMembers::get(), None);
+		let _ = MemberLabels::<T>::clear_prefix(multisig_id, T::MaxMembers::get(), None);
+		MinFundingAmounts::<T>::remove(multisig_id);
+		MaxPendingTransactions::<T>::remove(multisig_id);
+		PendingTransactionCount::<T>::remove(multisig_id);
+		TransactionNonce::<T>::remove(multisig_id);
+		Sunsets::<T>::remove(multisig_id);
+		MultisigsByCreator::<T>::mutate(&multisig.creator, |created| {
+			created.retain(|id| id != multisig_id)
+		});
+		Ok(to)
+	}
+	/// Whether the multisig has reached its sunset and accepts no new proposals.
+	pub fn is_sunset(multisig_id: &T::AccountId) -> bool {
+		Sunsets::<T>::get(multisig_id)
+			.is_some_and(|sunset| frame_system::Pallet::<T>::block_number() >= sunset.sunset_at)
+	}
+	/// Wind down a multisig past its sunset, sweeping its funds as set with the sunset.
+	pub fn do_wind_down(who: &T::AccountId, multisig_id: &T::AccountId) -> DispatchResult {
+		let sunset = Sunsets::<T>::get(multisig_id).ok_or(Error::<T>::SunsetNotReached)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= sunset.sunset_at,
+			Error::<T>::SunsetNotReached
+		);
+		let to = Self::dissolve_multisig(who, multisig_id, sunset.beneficiary)?;
+		Self::deposit_event(Event::MultisigWoundDown { multisig: multisig_id.clone(), to });
+		Ok(())
+	}
+	/// Wind down the multisigs past their sunset for as long as the remaining weight allows,
+	/// returning the weight used. Each idle block resumes after the last sunset checked by the
+	/// previous one, so every sunset is reached however many there are. A multisig too heavy to
+	/// wind down with the whole weight of an idle block is skipped until the next pass, so it
+	/// does not hold back the sunsets after it. A multisig failing to wind down is left to its
+	/// members.
+	pub fn wind_down_sunset_multisigs(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		// Reading and updating the cursor
+		let mut used = T::DbWeight::get().reads_writes(1, 1);
+		if used.any_gt(remaining_weight) {
+			return Weight::zero();
+		}
+		let sunsets = match SunsetCursor::<T>::get() {
+			Some(cursor) => Sunsets::<T>::iter_from(cursor),
+			None => Sunsets::<T>::iter(),
+		};
+		let mut checked = None;
+		let mut finished = true;
+		let mut due = Vec::new();
+		for (multisig_id, sunset) in sunsets {
+			// The sunset and the pending transactions limit the wind down weight depends on
+			let read = T::DbWeight::get().reads(2);
+			if used.saturating_add(read).any_gt(remaining_weight) {
+				finished = false;
+				break;
+			}
+			used.saturating_accrue(read);
+			if sunset.sunset_at <= now {
+				let wind_down = Self::dissolve_weight(&multisig_id);
+				if used.saturating_add(wind_down).any_gt(remaining_weight) {
+					// Leave the multisig to the next idle block, unless it did not fit this one
+					// on its own either
+					if !due.is_empty() {
+						finished = false;
+						break;
+					}
+				} else {
+					used.saturating_accrue(wind_down);
+					due.push(multisig_id.clone());
+				}
+			}
+			checked = Some(Sunsets::<T>::hashed_key_for(&multisig_id));
+		}
+		// Start over once the end is reached, or resume after the last sunset checked
+		if finished {
+			SunsetCursor::<T>::kill();
+		} else if let Some(checked) = checked {
+			SunsetCursor::<T>::put(checked);
+		}
+		// Wound down after the iteration as it removes the sunsets
+		for multisig_id in due {
+			let _ = with_storage_layer(|| Self::do_wind_down(&multisig_id, &multisig_id));
+		}
+		used
+	}
+	/// Whether the transaction is still pending past its expiry block.
+	pub fn is_expired(multisig_id: &T::AccountId, transaction_id: &T::Hash) -> bool {
+		Transactions::<T>::get(multisig_id, transaction_id).is_some_and(|transaction| {
+			transaction.status == TransactionStatus::Pending &&
+				transaction.expires_at < frame_system::Pallet::<T>::block_number()
+		})
+	}
+	/// The pending transactions past their expiry block, as `(multisig_id, transaction_id)`.
+	pub fn expired_transactions() -> impl Iterator<Item = (T::AccountId, T::Hash)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Transactions::<T>::iter().filter_map(move |(multisig_id, transaction_id, transaction)| {
+			(transaction.status == TransactionStatus::Pending && transaction.expires_at < now)
+				.then_some((multisig_id, transaction_id))
+		})
+	}
+	/// Remove an expired transaction along with its call.
+	pub fn do_prune_expired_transaction(
+		multisig_id: T::AccountId,
+		transaction_id: T::Hash,
+	) -> DispatchResult {
+		let transaction = Transactions::<T>::get(&multisig_id, &transaction_id)
+			.ok_or(Error::<T>::TransactionDoesNotExist)?;
+		// Ensure the transaction is still pending past its expiry block
+		ensure!(
+			transaction.status == TransactionStatus::Pending &&
+				transaction.expires_at < frame_system::Pallet::<T>::block_number(),
+			Error::<T>::TransactionNotExpired
+		);
+		Transactions::<T>::remove(&multisig_id, &transaction_id);
+		Self::remove_call(&multisig_id, &transaction_id, transaction.call_hash);
+		TransactionCategories::<T>::remove(&multisig_id, &transaction_id);
+		RevealedAt::<T>::remove(&multisig_id, &transaction_id);
+		PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+		Self::deposit_event(Event::TransactionExpired {
+			transaction: transaction_id,
+			multisig: multisig_id,
+			status: TransactionStatus::Expired,
+			call_hash: transaction.call_hash,
+		});
+		Ok(())
+	}
+	/// Whether the signal is past its expiry block.
+	pub fn is_signal_expired(multisig_id: &T::AccountId, signal_id: &T::Hash) -> bool {
+		Signals::<T>::get(multisig_id, signal_id)
+			.is_some_and(|signal| signal.expires_at < frame_system::Pallet::<T>::block_number())
+	}
+	/// The signals past their expiry block, as `(multisig_id, signal_id)`.
+	pub fn expired_signals() -> impl Iterator<Item = (T::AccountId, T::Hash)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Signals::<T>::iter().filter_map(move |(multisig_id, signal_id, signal)| {
+			(signal.expires_at < now).then_some((multisig_id, signal_id))
+		})
+	}
+	/// Remove an expired signal, freeing its pending slot.
+	pub fn do_prune_expired_signal(
+		multisig_id: T::AccountId,
+		signal_id: T::Hash,
+	) -> DispatchResult {
+		let signal =
+			Signals::<T>::get(&multisig_id, &signal_id).ok_or(Error::<T>::SignalDoesNotExist)?;
+		// Ensure the signal is past its expiry block
+		ensure!(
+			signal.expires_at < frame_system::Pallet::<T>::block_number(),
+			Error::<T>::SignalNotExpired
+		);
+		Signals::<T>::remove(&multisig_id, &signal_id);
+		PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+		Self::deposit_event(Event::SignalExpired { signal: signal_id, multisig: multisig_id });
+		Ok(())
+	}
+	/// Store the call of a transaction, indexed by its hash.
+	pub fn insert_call(
+		multisig_id: &T::AccountId,
+		transaction_id: &T::Hash,
+		call_hash: [u8; 32],
+		call: Box<<T as Config>::RuntimeCall>,
+	) {
+		TransactionCalls::<T>::insert(multisig_id, transaction_id, call);
+		CallLocations::<T>::insert(call_hash, (multisig_id.clone(), *transaction_id), ());
+	}
+	/// Remove the call of a transaction along with its index entry, returning the call.
+	pub fn remove_call(
+		multisig_id: &T::AccountId,
+		transaction_id: &T::Hash,
+		call_hash: [u8; 32],
+	) -> Option<Box<<T as Config>::RuntimeCall>> {
+		CallLocations::<T>::remove(call_hash, (multisig_id.clone(), *transaction_id));
+		TransactionCalls::<T>::take(multisig_id, transaction_id)
+	}
+	/// A stored call with the given hash, if any transaction holds one.
+	pub fn call_by_hash(call_hash: &[u8; 32]) -> Option<Box<<T as Config>::RuntimeCall>> {
+		let (multisig_id, transaction_id) =
+			CallLocations::<T>::iter_key_prefix(call_hash).next()?;
+		TransactionCalls::<T>::get(multisig_id, transaction_id)
+	}
+	/// Take up one of the pending slots of a multisig, which its transactions and signals share.
+	pub fn reserve_pending_slot(multisig_id: &T::AccountId) -> Result<(), Error<T>> {
+		// Ensure the multisig has room for another pending transaction
+		PendingTransactionCount::<T>::try_mutate(multisig_id, |count| {
+			ensure!(
+				*count < Self::max_pending_transactions(multisig_id),
+				Error::<T>::TooManyPendingTransactions
+			);
+			count.saturating_inc();
+			Ok(())
+		})
+	}
 	/// Build and store a proposed transaction.
 	pub fn build_transaction(
 		from: T::AccountId,
 		multisig_id: T::AccountId,
-		call: Box<<T as Config>::RuntimeCall>,
+		call: Option<Box<<T as Config>::RuntimeCall>>,
 		call_hash: [u8; 32],
+		threshold: Threshold,
+		total_weight: u32,
+		options: ProposalOptionsOf<T>,
 	) -> Result<(), Error<T>> {
-		let transaction_id = Self::generate_transaction_id(
-			from.clone(),
-			frame_system::Pallet::<T>::block_number(),
-			call_hash,
+		let created_at = frame_system::Pallet::<T>::block_number();
+		// Set the expiration block to the current block number plus the default expiration blocks
+		// count
+		let expires_at = created_at.saturating_add(T::DefaultExpirationBlocks::get());
+		// Ensure the execution window is not empty and closes before the transaction expires
+		let not_before = options.execute_not_before.unwrap_or(created_at);
+		let not_after = options.execute_not_after.unwrap_or(expires_at);
+		ensure!(
+			not_before <= not_after && not_after <= expires_at,
+			Error::<T>::InvalidExecutionWindow
 		);
+		// Ensure the multisig still accepts proposals
+		ensure!(!Self::is_sunset(&multisig_id), Error::<T>::MultisigSunset);
+		let transaction_id =
+			Self::generate_transaction_id(multisig_id.clone(), from.clone(), created_at, call_hash);
+		// Ensure the same proposal is not made twice in a block, which would overwrite its votes
+		ensure!(
+			!Transactions::<T>::contains_key(&multisig_id, &transaction_id),
+			Error::<T>::TransactionAlreadyExists
+		);
+		Self::reserve_pending_slot(&multisig_id)?;
+		let nonce = TransactionNonce::<T>::mutate(&multisig_id, |nonce| {
+			let current = *nonce;
+			*nonce = nonce.saturating_add(1);
+			current
+		});
+		let approvals = T::VoteWeightProvider::vote_weight(&from);
 		let mut votes = BoundedBTreeMap::new();
 		votes
-			.try_insert(from.clone(), Vote::Approve)
+			.try_insert(from.clone(), (Vote::Approve, approvals))
 			.map_err(|_| Error::<T>::VoteLimitReached)?;
+		// The approval of the proposer alone can already meet the threshold
+		let approved_at =
+			(approvals >= threshold.votes_required(total_weight)).then_some(created_at);
 		let transaction = Transaction {
 			proposer: from.clone(),
-			call,
 			call_hash,
 			status: TransactionStatus::Pending,
 			votes,
-			created_at: frame_system::Pallet::<T>::block_number(),
-			// Set the expiration block to the current block number plus the default expiration
-			// blocks count
-			expires_at: frame_system::Pallet::<T>::block_number()
-				.saturating_add(T::DefaultExpirationBlocks::get()),
+			tally: Tally { approvals, rejections: 0 },
+			created_at,
+			expires_at,
+			approved_at,
+			nonce,
+			threshold,
+			total_weight,
+			attachments: options.attachments.clone(),
+			execute_not_before: options.execute_not_before,
+			execute_not_after: options.execute_not_after,
 		};
 		Transactions::<T>::insert(&multisig_id, &transaction_id, transaction);
+		// The call of a private transaction is only stored once revealed
+		if let Some(call) = call {
+			Self::insert_call(&multisig_id, &transaction_id, call_hash, call);
+		}
+		if let Some(category) = options.category {
+			TransactionCategories::<T>::insert(&multisig_id, &transaction_id, category);
+		}
+		Self::record_activity(
+			&multisig_id,
+			Activity::Proposed { proposer: from.clone(), transaction: transaction_id },
+		);
 		Self::deposit_event(Event::TransactionCreated {
 			proposer: from,
 			transaction: transaction_id,
-			multisig: multisig_id,
+			multisig: multisig_id.clone(),
 			status: TransactionStatus::Pending,
 			call_hash,
+			attachments: options.attachments,
 		});
+		if let Some(approved_at) = approved_at {
+			Self::deposit_event(Event::ThresholdReached {
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals,
+				approved_at,
+			});
+		}
 		Ok(())
 	}
 }
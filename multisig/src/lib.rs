@@ -14,6 +14,40 @@
 //! * `create_multisig` - Create a new multisig account with a set of members and an approval/rejection threshold.
 //!   The creator must be one of the provided members and must provide a deposit.
 //!
+//! * `set_sunset` - Set the block from which a multisig account accepts no new proposals, along
+//!   with the beneficiary its funds are swept to on wind down. To be sent via dispatch call on
+//!   propose transaction only.
+//!
+//! * `wind_down` - Wind down a multisig account past its sunset, sweeping its funds to the
+//!   beneficiary or refunding the contributors. Multisigs past their sunset are also wound down
+//!   on idle blocks.
+//!
+//! * `contribute` - Fund a multisig account with a contribution held in escrow, refunded to the
+//!   contributor when the multisig is deleted.
+//!
+//! * `propose_private_transaction` - Propose a transaction by its call hash only, keeping the
+//!   call off-chain until it is revealed.
+//!
+//! * `reveal_call` - Reveal the call of a private transaction, which can be executed once it has
+//!   been revealed for `RevealPeriod` blocks.
+//!
+//! * `set_member_label` - Attach a short label to a member of a multisig account. To be sent via
+//!   dispatch call on propose transaction only.
+//!
+//! * `set_max_pending_transactions` - Change the maximum number of pending transactions of a
+//!   multisig account, holding a deposit for the transactions allowed above the default. To be
+//!   sent via dispatch call on propose transaction only.
+//!
+//! * `heartbeat` - Attest as a member that you still control your key, recording the block you
+//!   were last seen at.
+//!
+//! * `set_threshold` - Change the threshold of a multisig account between an absolute vote
+//!   weight and a percentage of the combined vote weight of the members. Must be dispatched by
+//!   the multisig itself through a proposal.
+//!
+//! * `create_multisigs` - Create a batch of multisig accounts at once, either all or nothing or
+//!   skipping the ones that fail.
+//!
 //! * `propose_transaction` - Propose a transaction to be executed by the multisig account. Only members
 //!   of the multisig group can propose, and the transaction is stored on-chain until it receives enough approvals/rejections.
 //!
@@ -22,19 +56,55 @@
 //!
 //! * `vote` - Submit a vote (approve or reject) for a proposed transaction. Only multisig members can vote.
 //!
-//! * `submit_transaction` - Submit and execute the transaction once it has reached the required number
-//!   of approvals. The proposed transaction can also be canceled if it has enough rejection votes when submitted.
+//! * `submit_transaction` - Submit and execute the stored call of a transaction once it has reached
+//!   the required number of approvals. The proposed transaction can also be canceled if it has enough rejection votes when submitted.
 //!
 //! * `cancel_transaction` - Cancel a proposed transaction. To be sent via dispatch call on propose
 //! transaction only.
 //!
 //! * `delete_multisig` - Delete a multisig account. To be sent via dispatch call on propose
 //! transaction only.
+//!
+//! * `fund_and_propose` - Fund the multisig account and propose a transaction atomically. Only
+//!   members of the multisig group can fund and propose in one step.
+//!
+//! * `set_min_funding_amount` - Override the minimum funding amount of a multisig account. To be
+//!   sent via dispatch call on propose transaction only.
+//!
+//! * `approve_with_signature` - Relay a member's approval signed off-chain over the proposal
+//!   digest of a transaction. See `Pallet::approval_payload` for the signed format.
+//!
+//! * `earmark_funds` - Hold funds of the multisig account under a named purpose. To be sent via
+//!   dispatch call on propose transaction only.
+//!
+//! * `release_earmark` - Release the funds held under a named purpose. To be sent via dispatch
+//!   call on propose transaction only.
+//!
+//! * `propose_transaction_with_options` - Propose a transaction with options such as the content
+//!   hashes of off-chain documents, e.g. invoices or contracts, justifying it, or the window of
+//!   blocks within which it can be executed.
+//!
+//! * `propose_signal` - Propose a bounded message to be voted on which dispatches nothing, giving
+//!   the multisig a binding on-chain signal. Signals are voted on with `vote_signal` and resolved
+//!   with `resolve_signal` once they reach either threshold.
+//!
+//! * `set_vote_delegate` - Set the account allowed to vote on behalf of a member, which votes with
+//!   `vote_as_delegate`. The vote is recorded as the member's own.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 mod impls;
+pub mod migrations;
+pub mod preimages;
+pub mod runtime_api;
+pub mod weights;
+pub use weights::WeightInfo;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -42,26 +112,58 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Provides the weight of a member's vote, allowing for token-weighted multisigs. The votes on a
+/// transaction keep the weight the voter had when casting them, while the votes on a signal are
+/// weighted when it is resolved.
+pub trait VoteWeight<AccountId> {
+	/// The weight of the given member's vote.
+	fn vote_weight(who: &AccountId) -> u32;
+}
+
+/// Every member's vote carries a weight of one.
+impl<AccountId> VoteWeight<AccountId> for () {
+	fn vote_weight(_who: &AccountId) -> u32 {
+		1
+	}
+}
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
+	use crate::VoteWeight;
+	use derive_where::derive_where;
 	use frame_support::{
 		dispatch::{DispatchResult, GetDispatchInfo, RawOrigin},
 		pallet_prelude::{ValueQuery, *},
+		storage::with_storage_layer,
 		traits::{
-			fungible::{self, hold::Mutate as HoldMutate, Inspect, Mutate},
-			tokens::{Fortitude, Precision, Preservation},
+			fungible::{self, hold::Mutate as HoldMutate},
+			tokens::Precision,
 		},
+		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
+	#[cfg(feature = "serde")]
+	use serde::{Deserialize, Serialize};
 	use sp_core::blake2_256;
-	use sp_runtime::{traits::Dispatchable, BoundedBTreeMap, BoundedBTreeSet, Saturating};
+	use sp_runtime::{
+		traits::{Dispatchable, IdentifyAccount, Verify},
+		BoundedBTreeMap, BoundedBTreeSet, Saturating,
+	};
 	use sp_std::prelude::*;
 
+	pub use multisig_primitives::{
+		MultisigAccount, Tally, Threshold, Transaction, TransactionStatus, Vote,
+	};
+
+	/// The in-code storage version.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	pub type BalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -85,6 +187,11 @@ pub mod pallet {
 		/// The reason for holding funds in the multisig account.
 		type RuntimeHoldReason: From<HoldReason>;
 
+		/// The overarching task type of the runtime.
+		type RuntimeTask: frame_support::traits::Task
+			+ IsType<<Self as frame_system::Config>::RuntimeTask>
+			+ From<Task<Self>>;
+
 		/// The default constant maximum number of members allowed in a multisig.
 		#[pallet::constant]
 		type MaxMembers: Get<u32>;
@@ -100,6 +207,91 @@ pub mod pallet {
 		/// The default constant of exipration blocks for a transaction;
 		#[pallet::constant]
 		type DefaultExpirationBlocks: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of multisigs a single account can have created at once.
+		#[pallet::constant]
+		type MaxMultisigsPerCreator: Get<u32>;
+
+		/// The default constant minimum amount accepted when funding a multisig.
+		#[pallet::constant]
+		type MinFundingAmount: Get<BalanceOf<Self>>;
+
+		/// The pallet identifier used to domain separate proposal digests.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The signature type used to approve transactions off-chain.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key type identifying the signer of an off-chain approval.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The weight of a member's vote. Use `()` for one vote per member.
+		type VoteWeightProvider: VoteWeight<Self::AccountId>;
+
+		/// The maximum length of the purpose an earmark of funds is named after.
+		#[pallet::constant]
+		type MaxEarmarkPurposeLength: Get<u32>;
+
+		/// The maximum number of content hashes a proposed transaction can carry.
+		#[pallet::constant]
+		type MaxAttachments: Get<u32>;
+
+		/// The maximum length of the message of a signal.
+		#[pallet::constant]
+		type MaxSignalLength: Get<u32>;
+
+		/// Whether the votes cast on a transaction within a block are reported by a single
+		/// `VotesRecorded` event at the end of the block instead of a `TransactionVoted` event each.
+		#[pallet::constant]
+		type AggregateVoteEvents: Get<bool>;
+
+		/// The maximum number of transactions whose votes are aggregated within a block, past
+		/// which the votes on other transactions are reported by a `TransactionVoted` event each.
+		#[pallet::constant]
+		type MaxAggregatedTransactions: Get<u32>;
+
+		/// The maximum number of entries kept in the activity history of a multisig.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+
+		/// The maximum number of multisigs that can be created in a single batch.
+		#[pallet::constant]
+		type MaxBatchCreate: Get<u32>;
+
+		/// The minimum number of blocks the call of a private transaction must be revealed for
+		/// before it can be executed.
+		#[pallet::constant]
+		type RevealPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum length of the label of a member.
+		#[pallet::constant]
+		type MaxLabelLength: Get<u32>;
+
+		/// The default maximum number of transactions and signals pending on a multisig at once.
+		#[pallet::constant]
+		type DefaultMaxPendingTransactions: Get<u32>;
+
+		/// The highest maximum number of transactions and signals a multisig can allow pending at
+		/// once, bounding the weight of deleting it.
+		#[pallet::constant]
+		type MaxPendingTransactionsLimit: Get<u32>;
+
+		/// The deposit held from the multisig account for every pending transaction it is allowed
+		/// above `DefaultMaxPendingTransactions`.
+		#[pallet::constant]
+		type PendingTransactionDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of accounts that can contribute to a single multisig.
+		#[pallet::constant]
+		type MaxContributors: Get<u32>;
+
+		/// The maximum number of purposes the funds of a single multisig can be earmarked under.
+		#[pallet::constant]
+		type MaxEarmarks: Get<u32>;
+
+		/// Weight information for the extrinsics of the pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	/// Reasons for placing a hold on funds.
@@ -107,55 +299,145 @@ pub mod pallet {
 	pub enum HoldReason {
 		#[codec(index = 0)]
 		MultisigCreationDeposit,
+		#[codec(index = 1)]
+		Earmark,
+		#[codec(index = 2)]
+		Contribution,
+		#[codec(index = 3)]
+		PendingLimitDeposit,
+	}
+
+	/// The purpose an earmark of multisig funds is named after.
+	pub type EarmarkPurpose<T> = BoundedVec<u8, <T as Config>::MaxEarmarkPurposeLength>;
+
+	/// The content hashes of the off-chain documents attached to a proposed transaction.
+	pub type Attachments<T> = BoundedVec<[u8; 32], <T as Config>::MaxAttachments>;
+
+	/// The category of a proposed transaction, e.g. `*b"upgrades"`. A transaction proposed under
+	/// a category is dispatched from an account derived from the multisig and the category.
+	pub type ProposalCategory = [u8; 8];
+
+	/// A short label of a member of a multisig, e.g. `b"cfo"`.
+	pub type MemberLabel<T> = BoundedVec<u8, <T as Config>::MaxLabelLength>;
+
+	/// The message of a signal voted on by the members of a multisig.
+	pub type SignalMessage<T> = BoundedVec<u8, <T as Config>::MaxSignalLength>;
+
+	/// The options a transaction can be proposed with.
+	pub type ProposalOptionsOf<T> =
+		ProposalOptions<BlockNumberFor<T>, <T as Config>::MaxAttachments>;
+
+	/// The sunset of a multisig.
+	pub type SunsetOf<T> = Sunset<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+	/// An entry of the activity history of a multisig.
+	pub type ActivityEntryOf<T> = ActivityEntry<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::Hash,
+		BlockNumberFor<T>,
+	>;
+
+	/// How a batch of multisig creations handles an item that fails.
+	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+	#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+	pub enum BatchMode {
+		/// Any failing item fails the whole batch, creating nothing.
+		AllOrNothing,
+		/// Failing items are skipped and reported, the others are still created.
+		BestEffort,
+	}
+
+	/// A step of the lifecycle of a multisig recorded in its activity history.
+	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+	#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+	pub enum Activity<AccountId, Balance, Hash> {
+		Created { creator: AccountId },
+		Funded { from: AccountId, amount: Balance },
+		Proposed { proposer: AccountId, transaction: Hash },
+		Voted { voter: AccountId, transaction: Hash, vote: Vote },
+		Executed { submitter: AccountId, transaction: Hash },
+		Rejected { submitter: AccountId, transaction: Hash },
+		Canceled { submitter: AccountId, transaction: Hash },
 	}
 
-	/// Voting options on a proposed transaction.
-	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
-	pub enum Vote {
-		Approve,
-		Reject,
+	/// An activity of a multisig along with the block it happened at.
+	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+	#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+	pub struct ActivityEntry<AccountId, Balance, Hash, BlockNumber> {
+		/// The block number at which the activity happened.
+		pub block: BlockNumber,
+		/// The activity.
+		pub activity: Activity<AccountId, Balance, Hash>,
 	}
 
-	/// Potential statuses a transaction can have.
-	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
-	pub enum TransactionStatus {
-		Pending,
-		Complete,
-		Canceled,
-		Rejected,
-		Expired,
+	/// The end of life of a time-boxed multisig.
+	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+	#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+	pub struct Sunset<AccountId, BlockNumber> {
+		/// The block number from which no new proposals are accepted and the multisig can be
+		/// wound down.
+		pub sunset_at: BlockNumber,
+		/// The account the funds are swept to on wind down once the contributions are refunded,
+		/// or the creator without one.
+		pub beneficiary: Option<AccountId>,
 	}
 
+	/// Optional settings a transaction can be proposed with.
 	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
-	#[scale_info(skip_type_params(MaxMembers))]
-	pub struct MultisigAccount<AccountId, MaxMembers, BlockNumber> {
-		/// The creator of the multisig.
-		pub creator: AccountId,
-		/// The members of the multisig.
-		pub members: BoundedBTreeSet<AccountId, MaxMembers>,
-		/// The number of members required to approve a transaction.
-		pub threshold: u32,
-		/// The block number at which the multisig was created.
-		pub created_at: BlockNumber,
+	#[derive_where(Debug, PartialEq, Eq; BlockNumber, MaxAttachments: Get<u32>)]
+	#[derive_where(Default)]
+	#[cfg_attr(
+		feature = "serde",
+		derive(Serialize, Deserialize),
+		serde(bound(
+			serialize = "BlockNumber: Serialize",
+			deserialize = "BlockNumber: Deserialize<'de>, MaxAttachments: Get<u32>"
+		))
+	)]
+	#[scale_info(skip_type_params(MaxAttachments))]
+	pub struct ProposalOptions<BlockNumber, MaxAttachments> {
+		/// The content hashes of off-chain documents justifying the transaction.
+		pub attachments: BoundedVec<[u8; 32], MaxAttachments>,
+		/// The block number before which the transaction can not be executed.
+		pub execute_not_before: Option<BlockNumber>,
+		/// The block number after which the transaction can no longer be executed.
+		pub execute_not_after: Option<BlockNumber>,
+		/// The category the transaction is dispatched under, if any.
+		pub category: Option<ProposalCategory>,
 	}
 
+	/// A binding message voted on by the members of a multisig which dispatches nothing.
 	#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
-	#[scale_info(skip_type_params(MaxMembers))]
-	pub struct Transaction<AccountId, RuntimeCall, MaxMembers, BlockNumber> {
-		/// The proposer of the transaction.
+	#[derive_where(
+		Debug, PartialEq, Eq;
+		AccountId, MaxMembers: Get<u32>, MaxSignalLength: Get<u32>, BlockNumber
+	)]
+	#[cfg_attr(
+		feature = "serde",
+		derive(Serialize, Deserialize),
+		serde(bound(
+			serialize = "AccountId: Serialize, BlockNumber: Serialize",
+			deserialize = "AccountId: Deserialize<'de> + Ord, MaxMembers: Get<u32> + Clone, \
+				MaxSignalLength: Get<u32>, BlockNumber: Deserialize<'de>"
+		))
+	)]
+	#[scale_info(skip_type_params(MaxMembers, MaxSignalLength))]
+	pub struct Signal<AccountId, MaxMembers, MaxSignalLength, BlockNumber> {
+		/// The proposer of the signal.
 		pub proposer: AccountId,
-		/// The status of the transaction.
-		pub status: TransactionStatus,
-		/// The call to be executed.
-		pub call: RuntimeCall,
-		/// The hash of the call.
-		pub call_hash: [u8; 32],
-		/// The number of votes proposed on a transaction.
-		pub votes: BoundedBTreeMap<AccountId, Vote, MaxMembers>,
-		/// The block number at which the transaction was created.
+		/// The message of the signal.
+		pub message: BoundedVec<u8, MaxSignalLength>,
+		/// The votes cast on the signal, each with the weight its voter had when casting it.
+		pub votes: BoundedBTreeMap<AccountId, (Vote, u32), MaxMembers>,
+		/// The block number at which the signal was proposed.
 		pub created_at: BlockNumber,
-		/// The block number at which the transaction was approved.
+		/// The block number at which the signal expires.
 		pub expires_at: BlockNumber,
+		/// The threshold of the multisig at the time the signal was proposed.
+		pub threshold: Threshold,
+		/// The combined vote weight of the members at the time the signal was proposed.
+		pub total_weight: u32,
 	}
 
 	/// The set of multisigs in storage.
@@ -167,10 +449,172 @@ pub mod pallet {
 		MultisigAccount<T::AccountId, T::MaxMembers, BlockNumberFor<T>>,
 	>;
 
+	/// The members of each multisig, maintained alongside `Multisigs` for cheap membership checks.
+	#[pallet::storage]
+	pub type Members<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The account each member of a multisig has delegated their vote to.
+	#[pallet::storage]
+	pub type VoteDelegates<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// The most recent activity of each multisig, oldest first.
+	#[pallet::storage]
+	pub type History<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ActivityEntryOf<T>, T::MaxHistoryLength>,
+	>;
+
 	/// The nonce for multisig account generation.
 	#[pallet::storage]
 	pub type MultisigNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// The nonce for proposed transactions of each multisig.
+	#[pallet::storage]
+	pub type TransactionNonce<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// The funds of each multisig held under a named purpose.
+	#[pallet::storage]
+	pub type Earmarks<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		EarmarkPurpose<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The number of purposes the funds of each multisig are earmarked under.
+	#[pallet::storage]
+	pub type EarmarkCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The funds each account has contributed to a multisig, held in escrow on the multisig
+	/// account until it is deleted.
+	#[pallet::storage]
+	pub type Contributions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The number of accounts that have contributed to each multisig.
+	#[pallet::storage]
+	pub type ContributorCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The labels the multisigs attached to their members.
+	#[pallet::storage]
+	pub type MemberLabels<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		MemberLabel<T>,
+	>;
+
+	/// The block at which each member of a multisig last sent a heartbeat.
+	#[pallet::storage]
+	pub type LastSeen<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+	>;
+
+	/// The existing multisigs created by each account.
+	#[pallet::storage]
+	pub type MultisigsByCreator<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxMultisigsPerCreator>,
+		ValueQuery,
+	>;
+
+	/// The category of the transactions proposed under one, dispatched from the account derived
+	/// from the multisig and the category instead of the proposer.
+	#[pallet::storage]
+	pub type TransactionCategories<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		ProposalCategory,
+	>;
+
+	/// The block at which the call of each private transaction was revealed.
+	#[pallet::storage]
+	pub type RevealedAt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		BlockNumberFor<T>,
+	>;
+
+	/// The transactions holding each stored call, keyed by the call hash, so stored calls can be
+	/// looked up by hash as preimages.
+	#[pallet::storage]
+	pub type CallLocations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		[u8; 32],
+		Blake2_128Concat,
+		(T::AccountId, T::Hash),
+		(),
+	>;
+
+	/// The sunset of each time-boxed multisig.
+	#[pallet::storage]
+	pub type Sunsets<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, SunsetOf<T>>;
+
+	/// The raw key of the last sunset checked on idle, which the next idle block resumes after.
+	#[pallet::storage]
+	pub type SunsetCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
+	/// Per multisig overrides of the maximum number of pending transactions.
+	#[pallet::storage]
+	pub type MaxPendingTransactions<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
+	/// The number of transactions and signals pending on each multisig.
+	#[pallet::storage]
+	pub type PendingTransactionCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Per multisig overrides of the minimum funding amount.
+	#[pallet::storage]
+	pub type MinFundingAmounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
+
 	/// The set of transactions tied to the corresponding multisig account in storage.
 	#[pallet::storage]
 	pub type Transactions<T: Config> = StorageDoubleMap<
@@ -179,24 +623,105 @@ pub mod pallet {
 		T::AccountId,
 		Blake2_128Concat,
 		T::Hash,
-		Transaction<
-			T::AccountId,
-			Box<<T as Config>::RuntimeCall>,
-			T::MaxMembers,
-			BlockNumberFor<T>,
-		>,
+		Transaction<T::AccountId, T::MaxMembers, T::MaxAttachments, BlockNumberFor<T>>,
+	>;
+
+	/// The calls of the proposed transactions, only read when a transaction is executed.
+	#[pallet::storage]
+	pub type TransactionCalls<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		Box<<T as Config>::RuntimeCall>,
+	>;
+
+	/// The set of signals tied to the corresponding multisig account in storage.
+	#[pallet::storage]
+	pub type Signals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		Signal<T::AccountId, T::MaxMembers, T::MaxSignalLength, BlockNumberFor<T>>,
+	>;
+
+	/// The number of votes cast on each transaction in the current block, only tracked when vote
+	/// events are aggregated and cleared at the end of the block.
+	#[pallet::storage]
+	pub type BlockVoteCounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		u32,
+		ValueQuery,
 	>;
 
+	/// The number of transactions with votes counted in `BlockVoteCounts`, capped by
+	/// `MaxAggregatedTransactions` and cleared at the end of the block.
+	#[pallet::storage]
+	pub type AggregatedTransactionCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// Pallets use events to inform users when important changes are made.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A new mutlisig has been created.
 		NewMultisig { creator: T::AccountId, multisig: T::AccountId },
+		/// A multisig of a batch could not be created, only emitted in best effort mode.
+		MultisigCreationFailed { creator: T::AccountId, index: u32, error: DispatchError },
+		/// A batch of multisigs has been processed.
+		MultisigsBatchCreated { creator: T::AccountId, created: u32, failed: u32 },
+		/// The threshold of a multisig has been changed. Pending transactions keep the threshold
+		/// they were proposed under.
+		ThresholdChanged { multisig: T::AccountId, threshold: Threshold },
 		/// A multisig has been deleted.
 		MultisigDeleted { from: T::AccountId, multisig: T::AccountId },
+		/// The sunset of a multisig has been set or removed.
+		SunsetSet { multisig: T::AccountId, sunset: Option<SunsetOf<T>> },
+		/// A multisig past its sunset has been wound down and its funds swept to `to`.
+		MultisigWoundDown { multisig: T::AccountId, to: T::AccountId },
 		/// A multisig has been funded.
 		MultisigFunded { from: T::AccountId, to: T::AccountId, amount: BalanceOf<T> },
+		/// Funds of a multisig have been earmarked for a purpose.
+		FundsEarmarked { multisig: T::AccountId, purpose: EarmarkPurpose<T>, amount: BalanceOf<T> },
+		/// An earmark of multisig funds has been released back to the spendable balance.
+		EarmarkReleased { multisig: T::AccountId, purpose: EarmarkPurpose<T>, amount: BalanceOf<T> },
+		/// Funds have been contributed to a multisig and held in escrow.
+		ContributionMade { from: T::AccountId, multisig: T::AccountId, amount: BalanceOf<T> },
+		/// A contribution has been refunded on deletion of the multisig.
+		ContributionRefunded { to: T::AccountId, multisig: T::AccountId, amount: BalanceOf<T> },
+		/// A pending transaction past its expiry block has been pruned.
+		TransactionExpired {
+			transaction: T::Hash,
+			multisig: T::AccountId,
+			status: TransactionStatus,
+			call_hash: [u8; 32],
+		},
+		/// The call of a private transaction has been revealed.
+		CallRevealed {
+			revealer: T::AccountId,
+			transaction: T::Hash,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		},
+		/// The maximum number of pending transactions of a multisig has been changed, along with
+		/// the deposit now held for it.
+		MaxPendingTransactionsSet { multisig: T::AccountId, limit: u32, deposit: BalanceOf<T> },
+		/// The label of a member has been set or removed.
+		MemberLabelSet {
+			multisig: T::AccountId,
+			member: T::AccountId,
+			label: Option<MemberLabel<T>>,
+		},
+		/// A member has attested they still control their key.
+		HeartbeatReceived { multisig: T::AccountId, member: T::AccountId },
+		/// The minimum funding amount of a multisig has been set or reset to the default.
+		MinFundingAmountSet { multisig: T::AccountId, amount: Option<BalanceOf<T>> },
 		/// A proposed transaction has been created.
 		TransactionCreated {
 			proposer: T::AccountId,
@@ -204,7 +729,37 @@ pub mod pallet {
 			multisig: T::AccountId,
 			status: TransactionStatus,
 			call_hash: [u8; 32],
+			attachments: Attachments<T>,
+		},
+		/// A signal has been proposed.
+		SignalProposed {
+			proposer: T::AccountId,
+			signal: T::Hash,
+			multisig: T::AccountId,
+			message: SignalMessage<T>,
+		},
+		/// A signal has been voted on.
+		SignalVoted { voter: T::AccountId, signal: T::Hash, multisig: T::AccountId, vote: Vote },
+		/// A signal has reached its approval threshold and is binding.
+		SignalPassed {
+			submitter: T::AccountId,
+			signal: T::Hash,
+			multisig: T::AccountId,
+			approvals: u32,
+			rejections: u32,
+		},
+		/// A signal has reached its rejection threshold.
+		SignalRejected {
+			submitter: T::AccountId,
+			signal: T::Hash,
+			multisig: T::AccountId,
+			approvals: u32,
+			rejections: u32,
 		},
+		/// A pending signal has been canceled along with its multisig.
+		SignalCanceled { signal: T::Hash, multisig: T::AccountId },
+		/// A pending signal past its expiry block has been pruned.
+		SignalExpired { signal: T::Hash, multisig: T::AccountId },
 		/// A proposed transaction has been voted on.
 		TransactionVoted {
 			voter: T::AccountId,
@@ -213,6 +768,21 @@ pub mod pallet {
 			vote: Vote,
 			call_hash: [u8; 32],
 		},
+		/// A member of a multisig has set or removed the delegate allowed to vote on their behalf.
+		VoteDelegateSet {
+			multisig: T::AccountId,
+			member: T::AccountId,
+			delegate: Option<T::AccountId>,
+		},
+		/// Votes have been cast on a proposed transaction within the block.
+		VotesRecorded { multisig: T::AccountId, transaction: T::Hash, count: u32 },
+		/// A proposed transaction has reached its approval threshold.
+		ThresholdReached {
+			transaction: T::Hash,
+			multisig: T::AccountId,
+			approvals: u32,
+			approved_at: BlockNumberFor<T>,
+		},
 		/// A proposed transaction has been submitted.
 		TransactionExecuted {
 			submitter: T::AccountId,
@@ -222,6 +792,17 @@ pub mod pallet {
 			rejections: u32,
 			status: TransactionStatus,
 			call_hash: [u8; 32],
+			approved_at: BlockNumberFor<T>,
+		},
+		/// A proposed transaction has been rejected by the members and removed without dispatch.
+		TransactionRejected {
+			submitter: T::AccountId,
+			transaction: T::Hash,
+			multisig: T::AccountId,
+			approvals: u32,
+			rejections: u32,
+			status: TransactionStatus,
+			call_hash: [u8; 32],
 		},
 		/// A proposed transaction has been canceled.
 		TransactionCanceled {
@@ -242,8 +823,10 @@ pub mod pallet {
 		ApprovalThresholdMet,
 		/// Creator must be a member of the multisig.
 		ProposerMustBeMember,
-		/// Threshold is too high compared to members.
+		/// Threshold is too high compared to the combined vote weight of the members.
 		ThresholdTooHigh,
+		/// Threshold requires no votes, letting the proposer alone approve or reject.
+		ThresholdTooLow,
 		/// Multisig does not exist.
 		MultisigDoesNotExist,
 		/// Transaction already exists.
@@ -268,69 +851,138 @@ pub mod pallet {
 		ThresholdNotReached,
 		/// Call hash does not match the expected.
 		MismatchingCallHash,
+		/// The creator has reached the maximum number of multisigs they can create.
+		TooManyMultisigs,
+		/// The fund amount is below the minimum funding amount of the multisig.
+		BelowMinimumFunding,
+		/// The off-chain approval signature is invalid.
+		InvalidSignature,
+		/// No funds are earmarked for the purpose.
+		EarmarkDoesNotExist,
+		/// The execution window is empty or does not end before the transaction expires.
+		InvalidExecutionWindow,
+		/// The execution window of the transaction has not opened yet.
+		ExecutionWindowNotOpen,
+		/// The execution window of the transaction has closed.
+		ExecutionWindowClosed,
+		/// The signal does not exist.
+		SignalDoesNotExist,
+		/// The signal already exists.
+		SignalAlreadyExists,
+		/// The signal has not expired yet.
+		SignalNotExpired,
+		/// The signal has passed its expiry block.
+		SignalExpired,
+		/// The account is not the vote delegate of the member.
+		NotAVoteDelegate,
+		/// The transaction has not expired yet.
+		TransactionNotExpired,
+		/// The transaction has passed its expiry block.
+		TransactionExpired,
+		/// The call of the private transaction has not been revealed.
+		CallNotRevealed,
+		/// The call of the transaction has already been revealed.
+		CallAlreadyRevealed,
+		/// The call of the private transaction has not been revealed for long enough.
+		RevealPeriodNotElapsed,
+		/// The multisig has reached its maximum number of pending transactions and signals.
+		TooManyPendingTransactions,
+		/// The pending transactions limit is above `MaxPendingTransactionsLimit`.
+		PendingLimitTooHigh,
+		/// The sunset block is not in the future.
+		InvalidSunset,
+		/// The multisig has reached its sunset and accepts no new proposals.
+		MultisigSunset,
+		/// The multisig has not reached its sunset.
+		SunsetNotReached,
+		/// The multisig has reached its maximum number of contributors.
+		TooManyContributors,
+		/// The multisig has reached its maximum number of earmarks.
+		TooManyEarmarks,
 	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Dispatch call function that creates a new multisig account. It requires the creator to
-		/// be a member, the threshold must be less than or equal to the number of members, and a
-		/// configurable deposit is required. The deposit will become a "Hold" and be returned to
-		/// the creator of the multisig in the instance of deletion.
-		#[pallet::call_index(0)]
-		#[pallet::weight(Weight::default())]
-		pub fn create_multisig(
-			origin: OriginFor<T>,
-			members: BoundedBTreeSet<T::AccountId, T::MaxMembers>,
-			threshold: Option<u32>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			// Ensure the creator is a member of the multisig
-			ensure!(members.contains(&who), Error::<T>::ProposerMustBeMember);
-			// Ensure the threshold is not too low
-			ensure!(
-				threshold.unwrap_or(T::DefaultThreshold::get()) <= members.len() as u32,
-				Error::<T>::ThresholdTooHigh
-			);
-			let deposit = T::MultisigDeposit::get();
-			// Ensure the signer has enough balance to create the multisig
-			ensure!(
-				T::NativeBalance::reducible_balance(
-					&who,
-					Preservation::Preserve,
-					Fortitude::Polite
-				) >= deposit,
-				Error::<T>::NotEnoughFunds
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// Reserve the weight of draining the votes aggregated within the block on finalize
+			if T::AggregateVoteEvents::get() {
+				let entries = u64::from(T::MaxAggregatedTransactions::get()).saturating_add(1);
+				T::DbWeight::get().reads_writes(entries, entries)
+			} else {
+				Weight::zero()
+			}
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			// Report the votes aggregated within the block, one event per transaction
+			for (multisig, transaction, count) in BlockVoteCounts::<T>::drain() {
+				Self::deposit_event(Event::VotesRecorded { multisig, transaction, count });
+			}
+			AggregatedTransactionCount::<T>::kill();
+		}
+
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::wind_down_sunset_multisigs(n, remaining_weight)
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::DefaultMaxPendingTransactions::get() <= T::MaxPendingTransactionsLimit::get(),
+				"The default pending transactions limit is above `MaxPendingTransactionsLimit`"
 			);
-			let nonce = MultisigNonce::<T>::get();
-			// Increment the multisig nonce
-			MultisigNonce::<T>::put(nonce + 1);
-			let multisig_id = Self::generate_multi_account_id(nonce);
-			// Use the passed threshold or the default
-			let threshold = threshold.unwrap_or(T::DefaultThreshold::get());
-			let multisig = MultisigAccount {
-				creator: who.clone(),
-				members,
-				threshold,
-				created_at: frame_system::Pallet::<T>::block_number(),
-			};
-			Multisigs::<T>::insert(&multisig_id, multisig);
-			// Transfer to multisig account add 1 to the deposit to cover the transfer fee
-			let total_deposit: BalanceOf<T> = deposit.saturating_add(1u32.into());
-			T::NativeBalance::transfer(
-				&who,
-				&multisig_id,
-				total_deposit,
-				Preservation::Expendable,
-			)?;
-			// Hold that amount in the multisig account as a "deposit"
-			T::NativeBalance::hold(
-				&HoldReason::MultisigCreationDeposit.into(),
-				&multisig_id,
-				deposit,
-			)?;
+		}
 
-			Self::deposit_event(Event::NewMultisig { creator: who.clone(), multisig: multisig_id });
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
 
+	#[pallet::tasks_experimental]
+	impl<T: Config> Pallet<T> {
+		/// Prune a pending transaction past its expiry block along with its call.
+		#[pallet::task_list(Pallet::<T>::expired_transactions())]
+		#[pallet::task_condition(|multisig_id, transaction_id| {
+			Pallet::<T>::is_expired(multisig_id, transaction_id)
+		})]
+		#[pallet::task_weight(T::DbWeight::get().reads_writes(3, 6))]
+		#[pallet::task_index(0)]
+		pub fn prune_expired_transaction(
+			multisig_id: T::AccountId,
+			transaction_id: T::Hash,
+		) -> DispatchResult {
+			Self::do_prune_expired_transaction(multisig_id, transaction_id)
+		}
+		/// Prune a pending signal past its expiry block.
+		#[pallet::task_list(Pallet::<T>::expired_signals())]
+		#[pallet::task_condition(|multisig_id, signal_id| {
+			Pallet::<T>::is_signal_expired(multisig_id, signal_id)
+		})]
+		#[pallet::task_weight(T::DbWeight::get().reads_writes(2, 2))]
+		#[pallet::task_index(1)]
+		pub fn prune_expired_signal(
+			multisig_id: T::AccountId,
+			signal_id: T::Hash,
+		) -> DispatchResult {
+			Self::do_prune_expired_signal(multisig_id, signal_id)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatch call function that creates a new multisig account. It requires the creator to
+		/// be a member, the threshold must be at least one and no more than the combined vote
+		/// weight of the members, and a configurable deposit is required. The deposit will become a "Hold" and be returned to
+		/// the creator of the multisig in the instance of deletion.
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::default())]
+		pub fn create_multisig(
+			origin: OriginFor<T>,
+			members: BoundedBTreeSet<T::AccountId, T::MaxMembers>,
+			threshold: Option<u32>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_create_multisig(who, members, threshold)?;
 			Ok(())
 		}
 		/// Dispatch call function the intentionally allows anyone to fund the multisig account
@@ -346,29 +998,13 @@ pub mod pallet {
 			// Ensure the fund amount is not zero
 			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
 			let who = ensure_signed(origin)?;
-			// Ensure the origin has enough balance to fund the multisig
-			ensure!(
-				T::NativeBalance::reducible_balance(
-					&who,
-					Preservation::Preserve,
-					Fortitude::Polite
-				) >= amount,
-				Error::<T>::NotEnoughFunds
-			);
-			let multisig =
-				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
-			// Transfer the funds to the multisig account
-			T::NativeBalance::transfer(&who, &multisig_id, amount, Preservation::Preserve)?;
-			// Add the new mulisig account to the mulisig storage
-			Multisigs::<T>::insert(&multisig_id, multisig);
-			Self::deposit_event(Event::MultisigFunded { from: who, to: multisig_id, amount });
-			Ok(())
+			Self::do_fund_multisig(who, multisig_id, amount)
 		}
 		/// Dispatch call function that proposes a transaction representing a call to be
 		/// dispatched. This call will be up for voting and depending on the results of the vote it
 		/// will wither be dispatched or rejected.
 		#[pallet::call_index(2)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::propose_transaction(T::MaxMembers::get()))]
 		pub fn propose_transaction(
 			origin: OriginFor<T>,
 			multisig_id: T::AccountId,
@@ -381,13 +1017,21 @@ pub mod pallet {
 			ensure!(multisig.members.contains(&who), Error::<T>::ProposerMustBeMember);
 			let call_hash = blake2_256(&call.encode());
 			// Build and store the transaction
-			Self::build_transaction(who, multisig_id, call, call_hash)?;
+			Self::build_transaction(
+				who,
+				multisig_id,
+				Some(call),
+				call_hash,
+				multisig.threshold,
+				Self::total_vote_weight(&multisig.members),
+				Default::default(),
+			)?;
 			Ok(())
 		}
 		/// Dispatch call function that allows a member of the multisig to vote either "Approve" or
 		/// "Reject" on the dispatch/submisison of a proposed transaction.
 		#[pallet::call_index(3)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::vote(T::MaxMembers::get()))]
 		pub fn vote(
 			origin: OriginFor<T>,
 			multisig_id: T::AccountId,
@@ -395,103 +1039,116 @@ pub mod pallet {
 			vote: Vote,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let multisig =
-				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
-			// Ensure the proposer is a member of the multisig
-			ensure!(multisig.members.contains(&who), Error::<T>::NotAMember);
-			Transactions::<T>::try_mutate(
-				&multisig_id,
-				&transaction_id,
-				|maybe_transaction| -> Result<(), Error<T>> {
-					let transaction =
-						maybe_transaction.as_mut().ok_or(Error::<T>::TransactionDoesNotExist)?;
-					// Ensure the transaction has a "Pending" status
-					ensure!(
-						transaction.status == TransactionStatus::Pending,
-						Error::<T>::TransactionNotPending
-					);
-					// Ensure the transaction has not already been voted on by the proposer
-					ensure!(!transaction.votes.contains_key(&who), Error::<T>::AlreadyVoted);
-					// Update the transaction with the new vote
-					transaction
-						.votes
-						.try_insert(who.clone(), vote.clone())
-						.map_err(|_| Error::<T>::VoteLimitReached)?;
-					Self::deposit_event(Event::TransactionVoted {
-						voter: who,
-						transaction: transaction_id,
-						multisig: multisig_id.clone(),
-						vote,
-						call_hash: transaction.call_hash,
-					});
-					Ok(())
-				},
-			)?;
-			Ok(())
+			Self::do_vote(who, multisig_id, transaction_id, vote)
 		}
 		/// Dispatch call function that allows a member of the multisig to attempt to submit a
 		/// proposed transaction. Depending on the results of the vote, the call will either be
-		/// dispatched, the call will be rejected or the call will return nothing if no threshold
-		/// has been broken yet. Both approval and rejection paths will result in the transaction
-		/// being removed from storage.
+		/// dispatched, the call will be rejected without being dispatched or the call will return
+		/// nothing if no threshold has been broken yet. Both approval and rejection paths will
+		/// result in the transaction being removed from storage. The call dispatched is the one
+		/// stored on proposal, `call_hash` must match its hash. The weight of the dispatched call
+		/// is not included.
 		#[pallet::call_index(4)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::submit_transaction(T::MaxMembers::get()))]
 		pub fn submit_transaction(
 			origin: OriginFor<T>,
 			multisig_id: T::AccountId,
 			transaction_id: T::Hash,
-			call: Box<<T as Config>::RuntimeCall>,
 			call_hash: [u8; 32],
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let multisig =
-				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
 			// Ensure the proposer is a member of the multisig
-			ensure!(multisig.members.contains(&who), Error::<T>::NotAMember);
-			// Ensure the trnsaction call hash matches the expected hash
-			ensure!(blake2_256(&call.encode()) == call_hash, Error::<T>::MismatchingCallHash);
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
 			let transaction = Transactions::<T>::get(&multisig_id, &transaction_id)
 				.ok_or(Error::<T>::TransactionDoesNotExist)?;
+			// Ensure the expected call hash matches the hash of the proposed call
+			ensure!(transaction.call_hash == call_hash, Error::<T>::MismatchingCallHash);
 			// Ensure the transaction has a "Pending" status
 			ensure!(
 				transaction.status == TransactionStatus::Pending,
 				Error::<T>::TransactionNotPending
 			);
-			let (approvals, rejections) =
-				Self::do_tally_votes(transaction.status.clone(), transaction.votes)?;
-			if approvals >= multisig.threshold {
-				let res =
-					call.clone().dispatch(RawOrigin::Signed(transaction.proposer.clone()).into());
-				res.map(|_| ()).map_err(|_e| Error::<T>::TransactionFailed)?;
+			// Ensure the transaction has not passed its expiry block
+			ensure!(
+				transaction.expires_at >= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::TransactionExpired
+			);
+			let Tally { approvals, rejections } = transaction.tally;
+			let votes_required = transaction.threshold.votes_required(transaction.total_weight);
+			if approvals >= votes_required {
+				// The block is recorded whenever the approvals first meet the threshold
+				let approved_at = transaction.approved_at.ok_or(Error::<T>::ThresholdNotReached)?;
+				// Ensure the transaction is submitted within its execution window
+				let now = frame_system::Pallet::<T>::block_number();
+				if let Some(not_before) = transaction.execute_not_before {
+					ensure!(now >= not_before, Error::<T>::ExecutionWindowNotOpen);
+				}
+				if let Some(not_after) = transaction.execute_not_after {
+					ensure!(now <= not_after, Error::<T>::ExecutionWindowClosed);
+				}
+				// Ensure the call of a private transaction has been revealed for long enough
+				ensure!(
+					TransactionCalls::<T>::contains_key(&multisig_id, &transaction_id),
+					Error::<T>::CallNotRevealed
+				);
+				if let Some(revealed_at) = RevealedAt::<T>::take(&multisig_id, &transaction_id) {
+					ensure!(
+						now >= revealed_at.saturating_add(T::RevealPeriod::get()),
+						Error::<T>::RevealPeriodNotElapsed
+					);
+				}
+				// Remove the transaction before dispatching so the call can not observe it pending
 				Transactions::<T>::remove(&multisig_id, &transaction_id);
+				PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+				let call = Self::remove_call(&multisig_id, &transaction_id, call_hash)
+					.ok_or(Error::<T>::TransactionDoesNotExist)?;
+				// Dispatch from the multisig account, or from the derived account of the category
+				// the transaction belongs to
+				let origin = match TransactionCategories::<T>::take(&multisig_id, &transaction_id) {
+					Some(category) => Self::category_account_id(&multisig_id, &category),
+					None => multisig_id.clone(),
+				};
+				let res = call.dispatch(RawOrigin::Signed(origin).into());
+				res.map(|_| ()).map_err(|_e| Error::<T>::TransactionFailed)?;
+				Self::record_activity(
+					&multisig_id,
+					Activity::Executed { submitter: who.clone(), transaction: transaction_id },
+				);
 				Self::deposit_event(Event::TransactionExecuted {
-					submitter: who.clone(),
+					submitter: who,
 					transaction: transaction_id,
-					multisig: multisig_id.clone(),
+					multisig: multisig_id,
 					approvals,
 					rejections,
 					status: TransactionStatus::Complete,
 					call_hash,
+					approved_at,
 				});
-			}
-			if rejections >= multisig.threshold {
-				let res = call.dispatch(RawOrigin::Signed(transaction.proposer.clone()).into());
-				res.map(|_| ()).map_err(|_e| Error::<T>::TransactionFailed)?;
+			} else if rejections >= votes_required {
+				// A rejected transaction is removed without dispatching its call
 				Transactions::<T>::remove(&multisig_id, &transaction_id);
-				Self::deposit_event(Event::TransactionExecuted {
+				PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+				Self::remove_call(&multisig_id, &transaction_id, call_hash);
+				TransactionCategories::<T>::remove(&multisig_id, &transaction_id);
+				RevealedAt::<T>::remove(&multisig_id, &transaction_id);
+				Self::record_activity(
+					&multisig_id,
+					Activity::Rejected { submitter: who.clone(), transaction: transaction_id },
+				);
+				Self::deposit_event(Event::TransactionRejected {
 					submitter: who,
 					transaction: transaction_id,
 					multisig: multisig_id,
 					approvals,
 					rejections,
-					status: TransactionStatus::Complete,
+					status: TransactionStatus::Rejected,
 					call_hash,
 				});
 			}
 			Ok(())
 		}
-		/// WARNING: Only meant to be executed via propose transaction call dispatch.
-		/// Dispatch funciton call to propose canceling an existing proposed transaction.
+		/// Dispatch funciton call to cancel an existing proposed transaction. Must be dispatched
+		/// by the multisig itself, through a proposal that reached its threshold.
 		#[pallet::call_index(5)]
 		#[pallet::weight(Weight::default())]
 		pub fn cancel_transaction(
@@ -499,15 +1156,24 @@ pub mod pallet {
 			multisig_id: T::AccountId,
 			transaction_id: T::Hash,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			let multisig =
-				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
-			// Ensure the proposer is a member of the multisig
-			ensure!(multisig.members.contains(&who), Error::<T>::NotAMember);
-			let transaction = Transactions::<T>::get(&multisig_id, &transaction_id)
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			let transaction = Transactions::<T>::take(&multisig_id, &transaction_id)
 				.ok_or(Error::<T>::TransactionDoesNotExist)?;
+			// Ensure the transaction has a "Pending" status
+			ensure!(
+				transaction.status == TransactionStatus::Pending,
+				Error::<T>::TransactionNotPending
+			);
+			Self::remove_call(&multisig_id, &transaction_id, transaction.call_hash);
+			TransactionCategories::<T>::remove(&multisig_id, &transaction_id);
+			RevealedAt::<T>::remove(&multisig_id, &transaction_id);
+			PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+			Self::record_activity(
+				&multisig_id,
+				Activity::Canceled { submitter: multisig_id.clone(), transaction: transaction_id },
+			);
 			Self::deposit_event(Event::TransactionCanceled {
-				submitter: who,
+				submitter: multisig_id.clone(),
 				transaction: transaction_id,
 				multisig: multisig_id.clone(),
 				status: TransactionStatus::Canceled,
@@ -515,40 +1181,604 @@ pub mod pallet {
 			});
 			Ok(())
 		}
-		/// WARNING: Only meant to be executed via propose transaction call dispatch.
 		/// Dispatch function call to delete a multisig account and release all of "Hold" funds.
-		/// The remaining funds including the hold will be sent to the creator of the account.
+		/// The contributions are refunded to their contributors, the remaining funds including
+		/// the hold will be sent to the creator of the account and any transactions still pending
+		/// are canceled. Must be dispatched by the multisig itself, through a proposal that
+		/// reached its threshold.
 		#[pallet::call_index(6)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(Pallet::<T>::dissolve_weight(multisig_id))]
 		pub fn delete_multisig(origin: OriginFor<T>, multisig_id: T::AccountId) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			Self::dissolve_multisig(&multisig_id, &multisig_id, None)?;
+			Self::deposit_event(Event::MultisigDeleted {
+				from: multisig_id.clone(),
+				multisig: multisig_id,
+			});
+			Ok(())
+		}
+		/// Dispatch call function that funds the multisig account and proposes a transaction in
+		/// a single atomic step. This guarantees the funds a proposal relies on are in place
+		/// before it can be voted on, and neither happens if the other fails.
+		#[pallet::call_index(7)]
+		#[pallet::weight(Weight::default())]
+		pub fn fund_and_propose(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			fund_amount: BalanceOf<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			// Ensure the fund amount is not zero
+			ensure!(!fund_amount.is_zero(), Error::<T>::ZeroAmount);
 			let who = ensure_signed(origin)?;
 			let multisig =
 				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
 			// Ensure the proposer is a member of the multisig
-			ensure!(multisig.members.contains(&who), Error::<T>::NotAMember);
-			// Release all the "Hold" funds from the multisig account
-			T::NativeBalance::release_all(
-				&HoldReason::MultisigCreationDeposit.into(),
-				&multisig_id,
-				Precision::BestEffort,
+			ensure!(multisig.members.contains(&who), Error::<T>::ProposerMustBeMember);
+			Self::do_fund_multisig(who.clone(), multisig_id.clone(), fund_amount)?;
+			let call_hash = blake2_256(&call.encode());
+			// Build and store the transaction
+			Self::build_transaction(
+				who,
+				multisig_id,
+				Some(call),
+				call_hash,
+				multisig.threshold,
+				Self::total_vote_weight(&multisig.members),
+				Default::default(),
 			)?;
-			// All funds in the multisig account to reap the account
-			let total_funds = T::NativeBalance::reducible_balance(
+			Ok(())
+		}
+		/// Dispatch function call to override the minimum funding amount of a multisig account.
+		/// Passing `None` resets the multisig back to the configured default. Must be dispatched
+		/// by the multisig itself, through a proposal that reached its threshold.
+		#[pallet::call_index(8)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_min_funding_amount(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			amount: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			MinFundingAmounts::<T>::set(&multisig_id, amount);
+			Self::deposit_event(Event::MinFundingAmountSet { multisig: multisig_id, amount });
+			Ok(())
+		}
+		/// Dispatch call function that records an approval a member signed off-chain over the
+		/// proposal digest of a transaction. Anyone can relay the signed approval, which allows
+		/// hardware wallets and custodians to approve without submitting an extrinsic.
+		#[pallet::call_index(9)]
+		#[pallet::weight(Weight::default())]
+		pub fn approve_with_signature(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			transaction_id: T::Hash,
+			signer: T::AccountId,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let transaction = Transactions::<T>::get(&multisig_id, &transaction_id)
+				.ok_or(Error::<T>::TransactionDoesNotExist)?;
+			let digest = Self::proposal_digest(
 				&multisig_id,
-				Preservation::Expendable,
-				Fortitude::Force,
+				transaction.nonce,
+				transaction.call_hash,
+				transaction.expires_at,
 			);
-			// Transfer the remaining funds including the deposit to the creator of the multisig
-			T::NativeBalance::transfer(
+			let payload = Self::approval_payload(digest);
+			// Ensure the signer approved this exact proposal on this chain
+			ensure!(signature.verify(&payload[..], &signer), Error::<T>::InvalidSignature);
+			Self::do_vote(signer, multisig_id, transaction_id, Vote::Approve)
+		}
+		/// Dispatch function call to earmark funds of the multisig account for a named purpose.
+		/// The funds are placed on "Hold" so the balances layer enforces the allocation until the
+		/// earmark is released. Must be dispatched by the multisig itself, through a proposal that
+		/// reached its threshold.
+		#[pallet::call_index(10)]
+		#[pallet::weight(Weight::default())]
+		pub fn earmark_funds(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			purpose: EarmarkPurpose<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Ensure the earmark amount is not zero
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			// Ensure the multisig has room for an earmark under a new purpose
+			if !Earmarks::<T>::contains_key(&multisig_id, &purpose) {
+				EarmarkCount::<T>::try_mutate(&multisig_id, |count| {
+					ensure!(*count < T::MaxEarmarks::get(), Error::<T>::TooManyEarmarks);
+					count.saturating_inc();
+					Ok::<_, Error<T>>(())
+				})?;
+			}
+			// Hold the earmarked amount in the multisig account
+			T::NativeBalance::hold(&HoldReason::Earmark.into(), &multisig_id, amount)
+				.map_err(|_| Error::<T>::NotEnoughFunds)?;
+			Earmarks::<T>::mutate(&multisig_id, &purpose, |earmarked| {
+				*earmarked = earmarked.saturating_add(amount)
+			});
+			Self::deposit_event(Event::FundsEarmarked { multisig: multisig_id, purpose, amount });
+			Ok(())
+		}
+		/// Dispatch function call to release all of the funds earmarked for a purpose back to the
+		/// spendable balance of the multisig account. Must be dispatched by the multisig itself,
+		/// through a proposal that reached its threshold.
+		#[pallet::call_index(11)]
+		#[pallet::weight(Weight::default())]
+		pub fn release_earmark(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			purpose: EarmarkPurpose<T>,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			let amount = Earmarks::<T>::take(&multisig_id, &purpose);
+			ensure!(!amount.is_zero(), Error::<T>::EarmarkDoesNotExist);
+			EarmarkCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+			T::NativeBalance::release(
+				&HoldReason::Earmark.into(),
 				&multisig_id,
-				&multisig.creator,
-				total_funds,
-				Preservation::Expendable,
-			)
-			.map_err(|_| Error::<T>::TransferFailed)?;
-			Multisigs::<T>::remove(&multisig_id);
-			Self::deposit_event(Event::MultisigDeleted { from: who, multisig: multisig_id });
+				amount,
+				Precision::Exact,
+			)?;
+			Self::deposit_event(Event::EarmarkReleased { multisig: multisig_id, purpose, amount });
 			Ok(())
 		}
+		/// Dispatch call function that proposes a transaction like `propose_transaction`, with
+		/// options such as the content hashes of off-chain documents justifying it and the window
+		/// of blocks within which it can be executed once approved.
+		#[pallet::call_index(12)]
+		#[pallet::weight(Weight::default())]
+		pub fn propose_transaction_with_options(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			options: ProposalOptionsOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let multisig =
+				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
+			// Ensure the proposer is a member of the multisig
+			ensure!(multisig.members.contains(&who), Error::<T>::ProposerMustBeMember);
+			let call_hash = blake2_256(&call.encode());
+			// Build and store the transaction
+			Self::build_transaction(
+				who,
+				multisig_id,
+				Some(call),
+				call_hash,
+				multisig.threshold,
+				Self::total_vote_weight(&multisig.members),
+				options,
+			)?;
+			Ok(())
+		}
+		/// Dispatch call function that proposes a signal, a bounded message voted on like a
+		/// transaction which dispatches nothing once passed. The proposer approves it.
+		#[pallet::call_index(13)]
+		#[pallet::weight(Weight::default())]
+		pub fn propose_signal(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			message: SignalMessage<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let multisig =
+				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
+			// Ensure the proposer is a member of the multisig
+			ensure!(multisig.members.contains(&who), Error::<T>::ProposerMustBeMember);
+			// Ensure the multisig still accepts proposals
+			ensure!(!Self::is_sunset(&multisig_id), Error::<T>::MultisigSunset);
+			let created_at = frame_system::Pallet::<T>::block_number();
+			let signal_id = Self::generate_transaction_id(
+				multisig_id.clone(),
+				who.clone(),
+				created_at,
+				blake2_256(&message),
+			);
+			ensure!(
+				!Signals::<T>::contains_key(&multisig_id, &signal_id),
+				Error::<T>::SignalAlreadyExists
+			);
+			Self::reserve_pending_slot(&multisig_id)?;
+			let mut votes = BoundedBTreeMap::new();
+			votes
+				.try_insert(who.clone(), (Vote::Approve, T::VoteWeightProvider::vote_weight(&who)))
+				.map_err(|_| Error::<T>::VoteLimitReached)?;
+			let signal = Signal {
+				proposer: who.clone(),
+				message: message.clone(),
+				votes,
+				created_at,
+				expires_at: created_at.saturating_add(T::DefaultExpirationBlocks::get()),
+				threshold: multisig.threshold,
+				total_weight: Self::total_vote_weight(&multisig.members),
+			};
+			Signals::<T>::insert(&multisig_id, &signal_id, signal);
+			Self::deposit_event(Event::SignalProposed {
+				proposer: who,
+				signal: signal_id,
+				multisig: multisig_id,
+				message,
+			});
+			Ok(())
+		}
+		/// Dispatch call function that allows a member of the multisig to vote either "Approve" or
+		/// "Reject" on a pending signal.
+		#[pallet::call_index(14)]
+		#[pallet::weight(Weight::default())]
+		pub fn vote_signal(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			signal_id: T::Hash,
+			vote: Vote,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the voter is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+			Signals::<T>::try_mutate(&multisig_id, &signal_id, |maybe_signal| -> DispatchResult {
+				let signal = maybe_signal.as_mut().ok_or(Error::<T>::SignalDoesNotExist)?;
+				// Ensure the signal has not passed its expiry block
+				ensure!(
+					signal.expires_at >= frame_system::Pallet::<T>::block_number(),
+					Error::<T>::SignalExpired
+				);
+				// Ensure the signal has not already been voted on by the member
+				ensure!(!signal.votes.contains_key(&who), Error::<T>::AlreadyVoted);
+				// Record the vote with the current weight of the voter
+				let weight = T::VoteWeightProvider::vote_weight(&who);
+				signal
+					.votes
+					.try_insert(who.clone(), (vote.clone(), weight))
+					.map_err(|_| Error::<T>::VoteLimitReached)?;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::SignalVoted {
+				voter: who,
+				signal: signal_id,
+				multisig: multisig_id,
+				vote,
+			});
+			Ok(())
+		}
+		/// Dispatch call function that resolves a signal once it has reached either its approval
+		/// or rejection threshold, removing it from storage.
+		#[pallet::call_index(15)]
+		#[pallet::weight(Weight::default())]
+		pub fn resolve_signal(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			signal_id: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let multisig =
+				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
+			// Ensure the submitter is a member of the multisig
+			ensure!(multisig.members.contains(&who), Error::<T>::NotAMember);
+			let signal = Signals::<T>::get(&multisig_id, &signal_id)
+				.ok_or(Error::<T>::SignalDoesNotExist)?;
+			// Ensure the signal has not passed its expiry block
+			ensure!(
+				signal.expires_at >= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::SignalExpired
+			);
+			// Each vote counts with the weight its voter had when casting it
+			let Tally { approvals, rejections } = Self::tally(&signal.votes);
+			// The signal resolves against the threshold it was proposed under
+			let votes_required = signal.threshold.votes_required(signal.total_weight);
+			if approvals >= votes_required {
+				Signals::<T>::remove(&multisig_id, &signal_id);
+				PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+				Self::deposit_event(Event::SignalPassed {
+					submitter: who,
+					signal: signal_id,
+					multisig: multisig_id,
+					approvals,
+					rejections,
+				});
+			} else if rejections >= votes_required {
+				Signals::<T>::remove(&multisig_id, &signal_id);
+				PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_dec());
+				Self::deposit_event(Event::SignalRejected {
+					submitter: who,
+					signal: signal_id,
+					multisig: multisig_id,
+					approvals,
+					rejections,
+				});
+			} else {
+				return Err(Error::<T>::ThresholdNotReached.into());
+			}
+			Ok(())
+		}
+		/// Dispatch call function that allows a member of the multisig to set, or remove with
+		/// `None`, the account allowed to vote on their behalf, e.g. a hot key of a cold key member.
+		#[pallet::call_index(16)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_vote_delegate(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			delegate: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the signer is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+			VoteDelegates::<T>::set(&multisig_id, &who, delegate.clone());
+			Self::deposit_event(Event::VoteDelegateSet {
+				multisig: multisig_id,
+				member: who,
+				delegate,
+			});
+			Ok(())
+		}
+		/// Dispatch call function that allows the vote delegate of a member to vote on their behalf.
+		/// The vote is recorded as the member's own.
+		#[pallet::call_index(17)]
+		#[pallet::weight(Weight::default())]
+		pub fn vote_as_delegate(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			member: T::AccountId,
+			transaction_id: T::Hash,
+			vote: Vote,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the signer is the vote delegate of the member
+			ensure!(
+				VoteDelegates::<T>::get(&multisig_id, &member) == Some(who),
+				Error::<T>::NotAVoteDelegate
+			);
+			Self::do_vote(member, multisig_id, transaction_id, vote)
+		}
+		/// Dispatch call function that creates a batch of multisig accounts, each the same way as
+		/// `create_multisig`. In `AllOrNothing` mode any failing item fails the whole batch, in
+		/// `BestEffort` mode failing items are reported and skipped.
+		#[pallet::call_index(18)]
+		#[pallet::weight(Weight::default())]
+		pub fn create_multisigs(
+			origin: OriginFor<T>,
+			multisigs: BoundedVec<
+				(BoundedBTreeSet<T::AccountId, T::MaxMembers>, Option<u32>),
+				T::MaxBatchCreate,
+			>,
+			mode: BatchMode,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut created: u32 = 0;
+			let mut failed: u32 = 0;
+			for (index, (members, threshold)) in multisigs.into_iter().enumerate() {
+				// Revert the writes of a failing item without reverting the whole batch
+				let result = with_storage_layer(|| {
+					Self::do_create_multisig(who.clone(), members, threshold)
+				});
+				match (result, &mode) {
+					(Ok(_), _) => created.saturating_inc(),
+					(Err(error), BatchMode::AllOrNothing) => return Err(error),
+					(Err(error), BatchMode::BestEffort) => {
+						failed.saturating_inc();
+						Self::deposit_event(Event::MultisigCreationFailed {
+							creator: who.clone(),
+							index: index as u32,
+							error,
+						});
+					},
+				}
+			}
+			Self::deposit_event(Event::MultisigsBatchCreated { creator: who, created, failed });
+			Ok(())
+		}
+		/// Dispatch function call to change the threshold of a multisig account, e.g. between an
+		/// absolute vote weight and a percentage of the combined vote weight of the members.
+		/// Pending transactions keep being evaluated against the threshold they were proposed
+		/// under. The threshold must require at least one vote and no more than the combined vote
+		/// weight. Must be dispatched by the multisig itself, through a proposal that reached its
+		/// threshold.
+		#[pallet::call_index(19)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_threshold(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			threshold: Threshold,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			Multisigs::<T>::try_mutate(&multisig_id, |maybe_multisig| -> DispatchResult {
+				let multisig = maybe_multisig.as_mut().ok_or(Error::<T>::MultisigDoesNotExist)?;
+				// Ensure the threshold can be reached by the combined vote weight of the members
+				let total_weight = Self::total_vote_weight(&multisig.members);
+				let votes_required = threshold.votes_required(total_weight);
+				ensure!(votes_required <= total_weight, Error::<T>::ThresholdTooHigh);
+				// Ensure the threshold requires at least one vote
+				ensure!(votes_required > 0, Error::<T>::ThresholdTooLow);
+				multisig.threshold = threshold;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ThresholdChanged { multisig: multisig_id, threshold });
+			Ok(())
+		}
+		/// Dispatch call function that funds the multisig account with a contribution held in
+		/// escrow and attributed to the contributor. Unlike `fund_multisig`, the contribution
+		/// cannot be spent by the multisig and is refunded to the contributor when the multisig
+		/// is deleted instead of being swept to its creator.
+		#[pallet::call_index(20)]
+		#[pallet::weight(Weight::default())]
+		pub fn contribute(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Ensure the contributed amount is not zero
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let who = ensure_signed(origin)?;
+			Self::do_contribute(who, multisig_id, amount)
+		}
+		/// Dispatch call function that members send periodically to attest they still control
+		/// their key, recording the current block as the last block they were seen at.
+		#[pallet::call_index(21)]
+		#[pallet::weight(Weight::default())]
+		pub fn heartbeat(origin: OriginFor<T>, multisig_id: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the sender is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+			LastSeen::<T>::insert(&multisig_id, &who, frame_system::Pallet::<T>::block_number());
+			Self::deposit_event(Event::HeartbeatReceived { multisig: multisig_id, member: who });
+			Ok(())
+		}
+		/// Dispatch call function that proposes a private transaction, of which only the call
+		/// hash is stored and voted on so sensitive payloads stay off-chain. The call must be
+		/// revealed with `reveal_call` at least `RevealPeriod` blocks before it can be executed,
+		/// giving the members time to verify it.
+		#[pallet::call_index(22)]
+		#[pallet::weight(Weight::default())]
+		pub fn propose_private_transaction(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			call_hash: [u8; 32],
+			options: ProposalOptionsOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let multisig =
+				Multisigs::<T>::get(&multisig_id).ok_or(Error::<T>::MultisigDoesNotExist)?;
+			// Ensure the proposer is a member of the multisig
+			ensure!(multisig.members.contains(&who), Error::<T>::ProposerMustBeMember);
+			// Build and store the transaction without its call
+			Self::build_transaction(
+				who,
+				multisig_id,
+				None,
+				call_hash,
+				multisig.threshold,
+				Self::total_vote_weight(&multisig.members),
+				options,
+			)?;
+			Ok(())
+		}
+		/// Dispatch call function that reveals the call of a private transaction. The call must
+		/// match the hash the transaction was proposed with.
+		#[pallet::call_index(23)]
+		#[pallet::weight(Weight::default())]
+		pub fn reveal_call(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			transaction_id: T::Hash,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the revealer is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+			let transaction = Transactions::<T>::get(&multisig_id, &transaction_id)
+				.ok_or(Error::<T>::TransactionDoesNotExist)?;
+			ensure!(
+				transaction.status == TransactionStatus::Pending,
+				Error::<T>::TransactionNotPending
+			);
+			ensure!(
+				!TransactionCalls::<T>::contains_key(&multisig_id, &transaction_id),
+				Error::<T>::CallAlreadyRevealed
+			);
+			let call_hash = blake2_256(&call.encode());
+			ensure!(transaction.call_hash == call_hash, Error::<T>::MismatchingCallHash);
+			Self::insert_call(&multisig_id, &transaction_id, call_hash, call);
+			RevealedAt::<T>::insert(
+				&multisig_id,
+				&transaction_id,
+				frame_system::Pallet::<T>::block_number(),
+			);
+			Self::deposit_event(Event::CallRevealed {
+				revealer: who,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				call_hash,
+			});
+			Ok(())
+		}
+		/// Dispatch function call to attach a short label to a member of a multisig account, so
+		/// UIs can display meaningful names. Passing `None` removes the label. Must be dispatched
+		/// by the multisig itself, through a proposal that reached its threshold.
+		#[pallet::call_index(24)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_member_label(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			member: T::AccountId,
+			label: Option<MemberLabel<T>>,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			// Ensure the labeled account is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &member), Error::<T>::NotAMember);
+			MemberLabels::<T>::set(&multisig_id, &member, label.clone());
+			Self::deposit_event(Event::MemberLabelSet { multisig: multisig_id, member, label });
+			Ok(())
+		}
+		/// Dispatch function call to change the maximum number of transactions pending on a
+		/// multisig account at once. Every pending transaction allowed above the default requires
+		/// a deposit held from the multisig account, which is topped up when the limit is raised
+		/// and refunded when it is lowered. Must be dispatched by the multisig itself, through a
+		/// proposal that reached its threshold.
+		#[pallet::call_index(25)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_max_pending_transactions(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			limit: u32,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			// Ensure the limit keeps the deletion of the multisig within bounds
+			ensure!(
+				limit <= T::MaxPendingTransactionsLimit::get(),
+				Error::<T>::PendingLimitTooHigh
+			);
+			// Ensure the transactions already pending still fit under the limit
+			ensure!(
+				PendingTransactionCount::<T>::get(&multisig_id) <= limit,
+				Error::<T>::TooManyPendingTransactions
+			);
+			let deposit = Self::adjust_pending_limit_deposit(&multisig_id, limit)?;
+			MaxPendingTransactions::<T>::insert(&multisig_id, limit);
+			Self::deposit_event(Event::MaxPendingTransactionsSet {
+				multisig: multisig_id,
+				limit,
+				deposit,
+			});
+			Ok(())
+		}
+		/// Dispatch function call to set or remove the sunset of a multisig account. From the
+		/// sunset block on no new proposals are accepted and the multisig can be wound down,
+		/// which the pallet also does on idle blocks. Must be dispatched by the multisig itself,
+		/// through a proposal that reached its threshold.
+		#[pallet::call_index(26)]
+		#[pallet::weight(Weight::default())]
+		pub fn set_sunset(
+			origin: OriginFor<T>,
+			multisig_id: T::AccountId,
+			sunset: Option<SunsetOf<T>>,
+		) -> DispatchResult {
+			Self::ensure_multisig_origin(origin, &multisig_id)?;
+			match &sunset {
+				Some(sunset) => {
+					ensure!(
+						sunset.sunset_at > frame_system::Pallet::<T>::block_number(),
+						Error::<T>::InvalidSunset
+					);
+					Sunsets::<T>::insert(&multisig_id, sunset);
+				},
+				None => Sunsets::<T>::remove(&multisig_id),
+			}
+			Self::deposit_event(Event::SunsetSet { multisig: multisig_id, sunset });
+			Ok(())
+		}
+		/// Dispatch call function to wind down a multisig account past its sunset. The
+		/// contributions are refunded to their contributors, the rest of the funds is swept to
+		/// the beneficiary of the sunset, or the creator without one, and the multisig is
+		/// removed. Any member can wind the multisig down, the sunset and its beneficiary having
+		/// been agreed on through a proposal.
+		#[pallet::call_index(27)]
+		#[pallet::weight(Pallet::<T>::dissolve_weight(multisig_id))]
+		pub fn wind_down(origin: OriginFor<T>, multisig_id: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Ensure the signer is a member of the multisig
+			ensure!(Members::<T>::contains_key(&multisig_id, &who), Error::<T>::NotAMember);
+			Self::do_wind_down(&who, &multisig_id)
+		}
 	}
 }
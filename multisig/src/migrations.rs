@@ -0,0 +1,214 @@
+//! Storage migrations for the multisig pallet.
+
+use frame_support::{
+	migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{BoundedBTreeMap, BoundedBTreeSet, Saturating};
+use sp_std::prelude::*;
+
+use super::*;
+
+/// The storage layout of the first release of the pallet, where a multisig only held its
+/// members and an absolute threshold and each transaction stored its call inline.
+pub mod v0 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct MultisigAccount<AccountId, MaxMembers: Get<u32>, BlockNumber> {
+		pub creator: AccountId,
+		pub members: BoundedBTreeSet<AccountId, MaxMembers>,
+		pub threshold: u32,
+		pub created_at: BlockNumber,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct Transaction<AccountId, RuntimeCall, MaxMembers: Get<u32>, BlockNumber> {
+		pub proposer: AccountId,
+		pub status: TransactionStatus,
+		pub call: RuntimeCall,
+		pub call_hash: [u8; 32],
+		pub votes: BoundedBTreeMap<AccountId, Vote, MaxMembers>,
+		pub created_at: BlockNumber,
+		pub expires_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub type Multisigs<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		MultisigAccount<
+			<T as frame_system::Config>::AccountId,
+			<T as Config>::MaxMembers,
+			BlockNumberFor<T>,
+		>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type Transactions<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		<T as frame_system::Config>::Hash,
+		Transaction<
+			<T as frame_system::Config>::AccountId,
+			Box<<T as Config>::RuntimeCall>,
+			<T as Config>::MaxMembers,
+			BlockNumberFor<T>,
+		>,
+	>;
+}
+
+/// Moves every multisig to an absolute `Threshold` and fills in the storage maintained
+/// alongside it, then moves every transaction of an existing multisig to the current layout:
+/// its call is stored apart, its votes are weighted as of the upgrade and it snapshots the
+/// threshold and combined vote weight of its multisig. Transactions of deleted multisigs are
+/// dropped.
+pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let (mut reads, mut writes) = (0u64, 0u64);
+		Multisigs::<T>::translate::<
+			v0::MultisigAccount<T::AccountId, T::MaxMembers, BlockNumberFor<T>>,
+			_,
+		>(|multisig_id, old| {
+			reads.saturating_inc();
+			for member in old.members.iter() {
+				Members::<T>::insert(&multisig_id, member, ());
+			}
+			// Creators past the per creator limit only have their oldest multisigs indexed
+			let _ = MultisigsByCreator::<T>::try_mutate(&old.creator, |created| {
+				created.try_push(multisig_id.clone())
+			});
+			History::<T>::insert(&multisig_id, BoundedVec::default());
+			reads.saturating_inc();
+			writes.saturating_accrue(old.members.len() as u64 + 3);
+			Some(MultisigAccount {
+				creator: old.creator,
+				members: old.members,
+				threshold: Threshold::Absolute(old.threshold),
+				created_at: old.created_at,
+			})
+		});
+		let now = frame_system::Pallet::<T>::block_number();
+		Transactions::<T>::translate::<
+			v0::Transaction<
+				T::AccountId,
+				Box<<T as Config>::RuntimeCall>,
+				T::MaxMembers,
+				BlockNumberFor<T>,
+			>,
+			_,
+		>(|multisig_id, transaction_id, old| {
+			reads.saturating_accrue(2);
+			writes.saturating_inc();
+			let multisig = Multisigs::<T>::get(&multisig_id)?;
+			let threshold = multisig.threshold;
+			let total_weight = Pallet::<T>::total_vote_weight(&multisig.members);
+			// The votes cast before the upgrade are weighted as of the upgrade
+			let mut tally = Tally::default();
+			let mut votes = BoundedBTreeMap::new();
+			for (voter, vote) in old.votes {
+				let weight = T::VoteWeightProvider::vote_weight(&voter);
+				match vote {
+					Vote::Approve => tally.approvals.saturating_accrue(weight),
+					Vote::Reject => tally.rejections.saturating_accrue(weight),
+				}
+				votes.try_insert(voter, (vote, weight)).ok()?;
+			}
+			let approved_at =
+				(tally.approvals >= threshold.votes_required(total_weight)).then_some(now);
+			let nonce = TransactionNonce::<T>::mutate(&multisig_id, |nonce| {
+				let current = *nonce;
+				*nonce = nonce.saturating_add(1);
+				current
+			});
+			if old.status == TransactionStatus::Pending {
+				PendingTransactionCount::<T>::mutate(&multisig_id, |count| count.saturating_inc());
+			}
+			Pallet::<T>::insert_call(&multisig_id, &transaction_id, old.call_hash, old.call);
+			reads.saturating_accrue(2);
+			writes.saturating_accrue(4);
+			Some(Transaction {
+				proposer: old.proposer,
+				status: old.status,
+				call_hash: old.call_hash,
+				votes,
+				tally,
+				created_at: old.created_at,
+				expires_at: old.expires_at,
+				approved_at,
+				nonce,
+				threshold,
+				total_weight,
+				attachments: Default::default(),
+				execute_not_before: None,
+				execute_not_after: None,
+			})
+		});
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		// Every stored value must decode under the old layout, or it would be lost
+		let multisigs = v0::Multisigs::<T>::iter_keys().count() as u32;
+		ensure!(
+			v0::Multisigs::<T>::iter().count() as u32 == multisigs,
+			"A multisig does not decode as v0"
+		);
+		let transactions = v0::Transactions::<T>::iter_keys().count() as u32;
+		ensure!(
+			v0::Transactions::<T>::iter().count() as u32 == transactions,
+			"A transaction does not decode as v0"
+		);
+		// Only the transactions of existing multisigs are kept
+		let kept = v0::Transactions::<T>::iter_keys()
+			.filter(|(multisig_id, _)| v0::Multisigs::<T>::contains_key(multisig_id))
+			.count() as u32;
+		Ok((multisigs, kept).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let (multisigs, kept) = <(u32, u32)>::decode(&mut &state[..])
+			.map_err(|_| "The pre-upgrade state does not decode")?;
+		ensure!(
+			Multisigs::<T>::iter().count() as u32 == multisigs,
+			"A multisig was lost or does not decode as v1"
+		);
+		ensure!(
+			Transactions::<T>::iter().count() as u32 == kept,
+			"A transaction was lost or does not decode as v1"
+		);
+		for (multisig_id, multisig) in Multisigs::<T>::iter() {
+			ensure!(
+				multisig
+					.members
+					.iter()
+					.all(|member| Members::<T>::contains_key(&multisig_id, member)),
+				"The members of a multisig were not indexed"
+			);
+		}
+		for (multisig_id, transaction_id, _) in Transactions::<T>::iter() {
+			ensure!(
+				TransactionCalls::<T>::contains_key(&multisig_id, &transaction_id),
+				"The call of a transaction was lost"
+			);
+		}
+		Pallet::<T>::do_try_state()
+	}
+}
+
+/// Migrates the pallet storage from version 0 to version 1, only running when the on-chain
+/// storage version is 0.
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	InnerMigrateV0ToV1<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
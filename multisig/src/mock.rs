@@ -2,13 +2,14 @@ use std::collections::BTreeSet;
 
 use crate as pallet_multisig;
 use frame_support::{
-	derive_impl,
+	derive_impl, parameter_types,
 	traits::{ConstU128, ConstU16, ConstU32, ConstU64},
-	BoundedBTreeSet,
+	BoundedBTreeSet, PalletId,
 };
 use pallet_balances::Call as BalancesCall;
 use sp_core::H256;
 use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 	BuildStorage,
 };
@@ -19,6 +20,23 @@ pub const DEFAULT_THRESHOLD: u32 = 6;
 pub const MAX_MEMBERS: u32 = 10;
 pub const MULTISIG_DEPOSIT: u128 = 20;
 pub const DEFAULT_EXPIRATION_BLOCKS: u64 = 100;
+pub const MAX_MULTISIGS_PER_CREATOR: u32 = 3;
+pub const MIN_FUNDING_AMOUNT: u128 = 10;
+pub const MAX_EARMARK_PURPOSE_LENGTH: u32 = 32;
+pub const MAX_ATTACHMENTS: u32 = 4;
+pub const MAX_SIGNAL_LENGTH: u32 = 64;
+pub const MAX_HISTORY_LENGTH: u32 = 5;
+pub const MAX_BATCH_CREATE: u32 = 3;
+pub const REVEAL_PERIOD: u64 = 5;
+pub const MAX_LABEL_LENGTH: u32 = 16;
+pub const DEFAULT_MAX_PENDING_TRANSACTIONS: u32 = 5;
+pub const MAX_PENDING_TRANSACTIONS_LIMIT: u32 = 10;
+pub const PENDING_TRANSACTION_DEPOSIT: u128 = 10;
+pub const MAX_CONTRIBUTORS: u32 = 2;
+pub const MAX_EARMARKS: u32 = 2;
+pub const MAX_AGGREGATED_TRANSACTIONS: u32 = 1;
+pub const HEAVY_MEMBER: u64 = 4;
+pub const HEAVY_MEMBER_WEIGHT: u32 = 3;
 
 frame_support::construct_runtime!(
 	pub enum Test {
@@ -71,15 +89,55 @@ impl pallet_balances::Config for Test {
 	type MaxFreezes = ConstU32<10>;
 }
 
+/// Gives `HEAVY_MEMBER` a vote weight of `HeavyMemberWeight` and everyone else a weight of one.
+pub struct MockVoteWeight;
+impl pallet_multisig::VoteWeight<u64> for MockVoteWeight {
+	fn vote_weight(who: &u64) -> u32 {
+		if *who == HEAVY_MEMBER {
+			HeavyMemberWeight::get()
+		} else {
+			1
+		}
+	}
+}
+
+parameter_types! {
+	pub const MultisigPalletId: PalletId = PalletId(*b"py/mltsg");
+	pub static AggregateVoteEvents: bool = false;
+	pub static HeavyMemberWeight: u32 = HEAVY_MEMBER_WEIGHT;
+}
+
 impl pallet_multisig::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type NativeBalance = Balances;
 	type RuntimeCall = RuntimeCall;
 	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeTask = RuntimeTask;
 	type MaxMembers = ConstU32<MAX_MEMBERS>;
 	type DefaultThreshold = ConstU32<DEFAULT_THRESHOLD>;
 	type MultisigDeposit = ConstU128<MULTISIG_DEPOSIT>;
 	type DefaultExpirationBlocks = ConstU64<DEFAULT_EXPIRATION_BLOCKS>;
+	type MaxMultisigsPerCreator = ConstU32<MAX_MULTISIGS_PER_CREATOR>;
+	type MinFundingAmount = ConstU128<MIN_FUNDING_AMOUNT>;
+	type PalletId = MultisigPalletId;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type VoteWeightProvider = MockVoteWeight;
+	type MaxEarmarkPurposeLength = ConstU32<MAX_EARMARK_PURPOSE_LENGTH>;
+	type MaxAttachments = ConstU32<MAX_ATTACHMENTS>;
+	type MaxSignalLength = ConstU32<MAX_SIGNAL_LENGTH>;
+	type AggregateVoteEvents = AggregateVoteEvents;
+	type MaxAggregatedTransactions = ConstU32<MAX_AGGREGATED_TRANSACTIONS>;
+	type MaxHistoryLength = ConstU32<MAX_HISTORY_LENGTH>;
+	type MaxBatchCreate = ConstU32<MAX_BATCH_CREATE>;
+	type RevealPeriod = ConstU64<REVEAL_PERIOD>;
+	type MaxLabelLength = ConstU32<MAX_LABEL_LENGTH>;
+	type DefaultMaxPendingTransactions = ConstU32<DEFAULT_MAX_PENDING_TRANSACTIONS>;
+	type MaxPendingTransactionsLimit = ConstU32<MAX_PENDING_TRANSACTIONS_LIMIT>;
+	type PendingTransactionDeposit = ConstU128<PENDING_TRANSACTION_DEPOSIT>;
+	type MaxContributors = ConstU32<MAX_CONTRIBUTORS>;
+	type MaxEarmarks = ConstU32<MAX_EARMARKS>;
+	type WeightInfo = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -0,0 +1,60 @@
+//! Preimage adapter over the calls stored by the multisig pallet.
+//!
+//! Proposals keep their call in `TransactionCalls` until executed. [`StoredCallPreimages`]
+//! exposes those calls through the standard preimage traits, so the scheduler or governance can
+//! look a stored proposal call up by its hash instead of noting the same bytes again.
+
+use codec::Encode;
+use frame_support::{
+	ensure,
+	traits::{FetchResult, QueryPreimage, StorePreimage},
+};
+use sp_core::H256;
+use sp_runtime::{traits::BlakeTwo256, DispatchError};
+use sp_std::{borrow::Cow, marker::PhantomData};
+
+use super::*;
+
+/// Serves the calls of pending transactions as preimages, hashed with `blake2_256` like the
+/// call hashes of the pallet.
+///
+/// The calls are owned by their transactions: requesting a preimage does not keep it around
+/// once the transaction holding it is executed, canceled or pruned, and only calls already
+/// stored by a transaction can be noted.
+pub struct StoredCallPreimages<T>(PhantomData<T>);
+
+impl<T: Config> QueryPreimage for StoredCallPreimages<T> {
+	type H = BlakeTwo256;
+
+	fn len(hash: &H256) -> Option<u32> {
+		Pallet::<T>::call_by_hash(&hash.0).map(|call| call.encoded_size() as u32)
+	}
+
+	fn fetch(hash: &H256, _len: Option<u32>) -> FetchResult {
+		Pallet::<T>::call_by_hash(&hash.0)
+			.map(|call| Cow::Owned(call.encode()))
+			.ok_or(DispatchError::Unavailable)
+	}
+
+	// Every stored call is wanted by the transaction holding it
+	fn is_requested(hash: &H256) -> bool {
+		CallLocations::<T>::iter_key_prefix(hash.0).next().is_some()
+	}
+
+	fn request(_hash: &H256) {}
+
+	fn unrequest(_hash: &H256) {}
+}
+
+impl<T: Config> StorePreimage for StoredCallPreimages<T> {
+	const MAX_LENGTH: usize = u32::MAX as usize;
+
+	fn note(bytes: Cow<[u8]>) -> Result<H256, DispatchError> {
+		let hash = H256(sp_core::blake2_256(&bytes));
+		// Only calls already held by a transaction are available
+		ensure!(Self::is_requested(&hash), DispatchError::Unavailable);
+		Ok(hash)
+	}
+
+	fn unnote(_hash: &H256) {}
+}
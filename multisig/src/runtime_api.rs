@@ -0,0 +1,39 @@
+//! Runtime API definition for the multisig pallet.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::ActivityEntry;
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the multisig pallet meant to be answered off-chain.
+	pub trait MultisigApi<AccountId, Balance, Call, Hash, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		Call: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Estimate the total fees of proposing `call` on the multisig, gathering enough votes to
+		/// reach its threshold and executing it, priced with the current weights and fee
+		/// multiplier, including the weight of `call` itself. Returns `None` if the multisig does
+		/// not exist.
+		fn estimate_lifecycle_fees(multisig: AccountId, call: Call) -> Option<Balance>;
+
+		/// The recorded activity of the multisig from `since_block` onwards, oldest first. The
+		/// response is bounded by the `MaxHistoryLength` of the pallet.
+		fn activity(
+			multisig: AccountId,
+			since_block: BlockNumber,
+		) -> Vec<ActivityEntry<AccountId, Balance, Hash, BlockNumber>>;
+
+		/// Every member of the multisig along with the label the multisig attached to them, if
+		/// any.
+		fn members(multisig: AccountId) -> Vec<(AccountId, Option<Vec<u8>>)>;
+
+		/// Every member of the multisig along with the block they last sent a heartbeat at, or
+		/// `None` if they never have.
+		fn liveness(multisig: AccountId) -> Vec<(AccountId, Option<BlockNumber>)>;
+	}
+}
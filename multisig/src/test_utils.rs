@@ -0,0 +1,114 @@
+//! Helpers for downstream runtime tests to set up multisig scenarios.
+//!
+//! The builder writes the multisig straight into storage, skipping the creation deposit and the
+//! extrinsic flow, so it is only meant for tests.
+
+use frame_support::{pallet_prelude::*, traits::fungible::Mutate};
+use sp_runtime::{traits::Zero, BoundedBTreeSet};
+use sp_std::prelude::*;
+
+use super::*;
+
+/// Builds a multisig directly in storage.
+///
+/// ```ignore
+/// let multisig_id = MultisigBuilder::<Runtime>::new()
+/// 	.members([alice, bob, charlie])
+/// 	.threshold(2)
+/// 	.funded(1_000)
+/// 	.build();
+/// ```
+pub struct MultisigBuilder<T: Config> {
+	creator: Option<T::AccountId>,
+	members: Vec<T::AccountId>,
+	threshold: Option<Threshold>,
+	funded: BalanceOf<T>,
+}
+
+impl<T: Config> Default for MultisigBuilder<T> {
+	fn default() -> Self {
+		Self { creator: None, members: Vec::new(), threshold: None, funded: Zero::zero() }
+	}
+}
+
+impl<T: Config> MultisigBuilder<T> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The creator of the multisig, defaulting to the first member.
+	pub fn creator(mut self, creator: T::AccountId) -> Self {
+		self.creator = Some(creator);
+		self
+	}
+
+	pub fn members(mut self, members: impl IntoIterator<Item = T::AccountId>) -> Self {
+		self.members = members.into_iter().collect();
+		self
+	}
+
+	/// The threshold of the multisig, defaulting to the configured `DefaultThreshold`.
+	pub fn threshold(mut self, threshold: impl Into<Threshold>) -> Self {
+		self.threshold = Some(threshold.into());
+		self
+	}
+
+	/// The free balance minted into the multisig account.
+	pub fn funded(mut self, amount: BalanceOf<T>) -> Self {
+		self.funded = amount;
+		self
+	}
+
+	/// Writes the multisig into storage and returns its account id.
+	///
+	/// Panics if no members were given or there are more than `MaxMembers`, if the creator is
+	/// not a member or if the threshold can not be reached by the combined vote weight of the
+	/// members or requires no votes, the same multisigs `create_multisig` refuses.
+	pub fn build(self) -> T::AccountId {
+		let members: BoundedBTreeSet<T::AccountId, T::MaxMembers> = self
+			.members
+			.into_iter()
+			.collect::<sp_std::collections::btree_set::BTreeSet<_>>()
+			.try_into()
+			.expect("too many members");
+		let creator = self
+			.creator
+			.or_else(|| members.iter().next().cloned())
+			.expect("a multisig needs at least one member");
+		assert!(members.contains(&creator), "the creator must be a member");
+		let threshold = self.threshold.unwrap_or(Threshold::Absolute(T::DefaultThreshold::get()));
+		let total_weight = Pallet::<T>::total_vote_weight(&members);
+		let votes_required = threshold.votes_required(total_weight);
+		assert!(
+			votes_required <= total_weight,
+			"the threshold is higher than the combined vote weight of the members"
+		);
+		assert!(votes_required > 0, "the threshold requires no votes");
+
+		let nonce = MultisigNonce::<T>::get();
+		let multisig_id = Pallet::<T>::generate_multi_account_id(nonce);
+		MultisigNonce::<T>::put(nonce + 1);
+		for member in members.iter() {
+			Members::<T>::insert(&multisig_id, member, ());
+		}
+		Multisigs::<T>::insert(
+			&multisig_id,
+			MultisigAccount {
+				creator: creator.clone(),
+				members,
+				threshold,
+				created_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+		MultisigsByCreator::<T>::mutate(&creator, |created| {
+			created
+				.try_push(multisig_id.clone())
+				.expect("too many multisigs for the creator")
+		});
+		History::<T>::insert(&multisig_id, BoundedVec::default());
+		if !self.funded.is_zero() {
+			T::NativeBalance::mint_into(&multisig_id, self.funded).expect("minting should work");
+		}
+		multisig_id
+	}
+}
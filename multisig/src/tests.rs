@@ -1,7 +1,18 @@
+use std::collections::BTreeSet;
+
 use crate::{mock::*, *};
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok, traits::fungible::Mutate, BoundedBTreeMap};
-use sp_core::blake2_256;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold, Mutate},
+		Get, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+	},
+	weights::Weight,
+	BoundedBTreeMap, BoundedBTreeSet, BoundedVec,
+};
+use sp_core::{blake2_256, H256};
+use sp_runtime::{testing::TestSignature, DispatchError, Percent};
 
 #[test]
 fn generate_multi_account_id_works() {
@@ -26,12 +37,32 @@ fn generate_transaction_id_works() {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
 		let proposer = 1;
-		let transaction_id =
-			Multisig::generate_transaction_id(proposer, System::block_number(), call_hash);
-		let regenerated =
-			Multisig::generate_transaction_id(proposer, System::block_number(), call_hash);
+		let multisig_id = 2;
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			proposer,
+			System::block_number(),
+			call_hash,
+		);
+		let regenerated = Multisig::generate_transaction_id(
+			multisig_id,
+			proposer,
+			System::block_number(),
+			call_hash,
+		);
 		// Check that the generated account ID is deterministic
 		assert_eq!(transaction_id, regenerated);
+		// Check that the same proposal on another multisig never collides
+		let other_multisig_id = 3;
+		assert_ne!(
+			transaction_id,
+			Multisig::generate_transaction_id(
+				other_multisig_id,
+				proposer,
+				System::block_number(),
+				call_hash,
+			)
+		);
 	});
 }
 
@@ -66,22 +97,36 @@ fn build_transaction_works() {
 		let amount: u128 = 1000u128.into();
 		let call = call_transfer(to, amount);
 		let call_hash = blake2_256(&call.encode());
-		assert_ok!(Multisig::build_transaction(from, multisig_id, call.clone(), call_hash));
+		assert_ok!(Multisig::build_transaction(
+			from,
+			multisig_id,
+			Some(call.clone()),
+			call_hash,
+			Threshold::Absolute(2),
+			3,
+			Default::default()
+		));
 		let transaction_id =
-			Multisig::generate_transaction_id(from, System::block_number(), call_hash);
+			Multisig::generate_transaction_id(multisig_id, from, System::block_number(), call_hash);
 		let new_transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
 			.expect("Transaction should exist");
 		assert_eq!(new_transaction.proposer, from);
 		assert_eq!(new_transaction.status, TransactionStatus::Pending);
-		assert_eq!(new_transaction.call, call);
+		assert_eq!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id), Some(call));
 		assert_eq!(new_transaction.call_hash, call_hash);
 		assert_eq!(new_transaction.votes.len(), 1);
-		assert_eq!(new_transaction.votes.get(&from), Some(&Vote::Approve));
+		assert_eq!(new_transaction.votes.get(&from), Some(&(Vote::Approve, 1)));
 		assert_eq!(new_transaction.created_at, System::block_number());
 		assert_eq!(
 			new_transaction.expires_at,
 			System::block_number().saturating_add(DEFAULT_EXPIRATION_BLOCKS)
 		);
+		assert_eq!(new_transaction.approved_at, None);
+		assert_eq!(new_transaction.nonce, 0);
+		assert_eq!(new_transaction.threshold, Threshold::Absolute(2));
+		assert_eq!(new_transaction.total_weight, 3);
+		assert!(new_transaction.attachments.is_empty());
+		assert_eq!(TransactionNonce::<Test>::get(&multisig_id), 1);
 		System::assert_last_event(
 			Event::TransactionCreated {
 				proposer: from,
@@ -89,6 +134,7 @@ fn build_transaction_works() {
 				multisig: multisig_id,
 				status: TransactionStatus::Pending,
 				call_hash,
+				attachments: BoundedVec::default(),
 			}
 			.into(),
 		);
@@ -104,17 +150,33 @@ fn create_new_multisig_works() {
 		Balances::set_balance(&creator, 1_000u128.into());
 		let members = generate_members();
 		let nonce = MultisigNonce::<Test>::get();
+		let next_multisig_id = Multisig::next_multisig_id();
 		assert_ok!(Multisig::create_multisig(
 			RuntimeOrigin::signed(creator),
 			members.clone(),
 			Some(2)
 		));
 		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		assert_eq!(multisig_id, next_multisig_id);
 		let new_multisig = Multisigs::<Test>::get(&multisig_id).expect("Multisig should exist");
 		assert_eq!(new_multisig.creator, creator);
 		assert_eq!(new_multisig.members, members);
-		assert_eq!(new_multisig.threshold, 2);
+		assert_eq!(new_multisig.threshold, Threshold::Absolute(2));
 		assert_eq!(new_multisig.created_at, System::block_number());
+		assert_eq!(
+			new_multisig,
+			MultisigAccount {
+				creator,
+				members: members.clone(),
+				threshold: Threshold::Absolute(2),
+				created_at: System::block_number(),
+			}
+		);
+		assert_ne!(new_multisig, MultisigAccount::default());
+		for member in members.iter() {
+			assert!(Members::<Test>::contains_key(&multisig_id, member));
+		}
+		assert!(!Members::<Test>::contains_key(&multisig_id, &4));
 		System::assert_last_event(Event::NewMultisig { creator, multisig: multisig_id }.into());
 	});
 }
@@ -172,8 +234,12 @@ fn propose_transaction_works() {
 			multisig_id,
 			call,
 		));
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
 		let new_transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
 			.expect("Transaction should exist");
 		assert_eq!(new_transaction.proposer, creator);
@@ -206,12 +272,144 @@ fn vote_on_transaction_works() {
 			multisig_id,
 			call,
 		));
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
 		assert_ok!(Multisig::vote(RuntimeOrigin::signed(2), multisig_id, transaction_id, vote));
 		let new_transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
 			.expect("Transaction should exist");
 		assert_eq!(new_transaction.votes.len(), 2);
+		assert_eq!(new_transaction.approved_at, Some(System::block_number()));
+		System::assert_last_event(
+			Event::ThresholdReached {
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals: 2,
+				approved_at: System::block_number(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn vote_below_threshold_does_not_set_approved_at() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(3)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.approved_at, None);
+		// The threshold is reached on a later block
+		System::set_block_number(5);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(3),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.approved_at, Some(5));
+	});
+}
+
+#[test]
+fn proposal_meeting_threshold_is_approved_at_once() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		let call = call_transfer(3, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		// The approval of the proposer alone meets the threshold
+		assert_eq!(transaction.approved_at, Some(1));
+		System::assert_last_event(
+			Event::ThresholdReached {
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals: 1,
+				approved_at: 1,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn propose_same_transaction_twice_in_a_block_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(3, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call.clone()
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		// Proposing it again would wipe the votes already cast
+		assert_noop!(
+			Multisig::propose_transaction(RuntimeOrigin::signed(creator), multisig_id, call),
+			Error::<Test>::TransactionAlreadyExists
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.votes.len(), 2);
 	});
 }
 
@@ -242,19 +440,28 @@ fn submit_proposed_transaction_works() {
 			multisig_id,
 			call.clone(),
 		));
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let multisig_balance = Balances::free_balance(&multisig_id);
 		assert_ok!(Multisig::submit_transaction(
 			RuntimeOrigin::signed(creator),
 			multisig_id,
 			transaction_id,
-			call,
 			call_hash
 		));
 		assert!(
 			Transactions::<Test>::get(&multisig_id, &transaction_id).is_none(),
 			"Transaction should be removed after submission"
 		);
+		assert!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id).is_none());
+		// The call is dispatched from the multisig account rather than the proposer
+		assert_eq!(Balances::free_balance(&to), amount);
+		assert_eq!(Balances::free_balance(&multisig_id), multisig_balance - amount);
+		assert_eq!(Balances::free_balance(&creator), 1_000_000 - MULTISIG_DEPOSIT - 1);
 		System::assert_last_event(
 			Event::TransactionExecuted {
 				submitter: creator,
@@ -264,6 +471,7 @@ fn submit_proposed_transaction_works() {
 				rejections: 0,
 				status: TransactionStatus::Complete,
 				call_hash,
+				approved_at: System::block_number(),
 			}
 			.into(),
 		);
@@ -285,12 +493,20 @@ fn cancel_proposed_transaction() {
 		let multisig_id = Multisig::generate_multi_account_id(nonce);
 		let proposed_call = call_transfer(to, amount);
 		let proposed_call_hash = blake2_256(&proposed_call.encode());
-		let proposed_transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), proposed_call_hash);
+		let proposed_transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			proposed_call_hash,
+		);
 		let call = call_cancel_transaction(multisig_id, proposed_transaction_id);
 		let call_hash = blake2_256(&call.encode());
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
 		// Set the balance of the multisig account to ensure it can fund the transaction
 		Balances::set_balance(&multisig_id, 1_000_000u128.into());
 		assert_ok!(Multisig::create_multisig(
@@ -298,20 +514,12 @@ fn cancel_proposed_transaction() {
 			members.clone(),
 			Some(2)
 		));
-		// Build and propose a transaction
-		assert_ok!(Multisig::build_transaction(
-			creator,
-			multisig_id,
-			proposed_call.clone(),
-			proposed_call_hash
-		));
+		// Propose a transaction and then the cancelation of it
 		assert_ok!(Multisig::propose_transaction(
 			RuntimeOrigin::signed(creator),
 			multisig_id,
 			proposed_call.clone(),
 		));
-		// Build and propose the cancelation transaction of an existing transaction
-		assert_ok!(Multisig::build_transaction(creator, multisig_id, call.clone(), call_hash));
 		assert_ok!(Multisig::propose_transaction(
 			RuntimeOrigin::signed(creator),
 			multisig_id,
@@ -333,16 +541,19 @@ fn cancel_proposed_transaction() {
 			RuntimeOrigin::signed(creator),
 			multisig_id,
 			transaction_id,
-			call,
 			call_hash
 		));
 		assert!(
 			Transactions::<Test>::get(&multisig_id, &transaction_id).is_none(),
 			"Transaction should be removed after cancellation"
 		);
+		assert!(
+			Transactions::<Test>::get(&multisig_id, &proposed_transaction_id).is_none(),
+			"Canceled transaction should be removed"
+		);
 		System::assert_has_event(
 			Event::TransactionCanceled {
-				submitter: creator,
+				submitter: multisig_id,
 				transaction: proposed_transaction_id,
 				multisig: multisig_id,
 				status: TransactionStatus::Canceled,
@@ -378,8 +589,12 @@ fn delete_multisig_works() {
 			multisig_id,
 			call.clone(),
 		));
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
 		assert_ok!(Multisig::vote(
 			RuntimeOrigin::signed(2),
 			multisig_id,
@@ -390,12 +605,12 @@ fn delete_multisig_works() {
 			RuntimeOrigin::signed(creator),
 			multisig_id,
 			transaction_id,
-			call,
 			call_hash
 		));
 		System::assert_has_event(
-			Event::MultisigDeleted { from: creator, multisig: multisig_id }.into(),
+			Event::MultisigDeleted { from: multisig_id, multisig: multisig_id }.into(),
 		);
+		assert_eq!(Members::<Test>::iter_prefix(&multisig_id).count(), 0);
 		System::assert_last_event(
 			Event::TransactionExecuted {
 				submitter: creator,
@@ -405,6 +620,7 @@ fn delete_multisig_works() {
 				rejections: 0,
 				status: TransactionStatus::Complete,
 				call_hash,
+				approved_at: System::block_number(),
 			}
 			.into(),
 		);
@@ -453,8 +669,12 @@ fn can_only_vote_once() {
 			multisig_id,
 			call,
 		));
-		let transaction_id =
-			Multisig::generate_transaction_id(creator, System::block_number(), call_hash);
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
 		assert_noop!(
 			Multisig::vote(RuntimeOrigin::signed(creator), multisig_id, transaction_id, vote),
 			Error::<Test>::AlreadyVoted
@@ -490,6 +710,39 @@ fn multisig_threshold_too_low() {
 			Multisig::create_multisig(RuntimeOrigin::signed(creator), members.clone(), Some(5)),
 			Error::<Test>::ThresholdTooHigh
 		);
+		// A threshold requiring no votes would let the proposer alone decide
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(0)),
+			Error::<Test>::ThresholdTooLow
+		);
+	});
+}
+
+#[test]
+fn multisig_threshold_is_checked_against_vote_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members_set: BTreeSet<u64> = vec![1, 2, HEAVY_MEMBER].into_iter().collect();
+		let members =
+			BoundedBTreeSet::try_from(members_set).expect("Should have a valid members set");
+		let total_weight = 2 + HEAVY_MEMBER_WEIGHT;
+		assert_eq!(Multisig::total_vote_weight(&members), total_weight);
+		// The threshold can exceed the member count as long as the votes can reach it
+		assert_noop!(
+			Multisig::create_multisig(
+				RuntimeOrigin::signed(creator),
+				members.clone(),
+				Some(total_weight + 1)
+			),
+			Error::<Test>::ThresholdTooHigh
+		);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members,
+			Some(total_weight)
+		));
 	});
 }
 
@@ -579,3 +832,2542 @@ fn propose_transaction_non_member() {
 		);
 	});
 }
+
+#[test]
+fn multisig_creator_limit_reached() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+
+		for _ in 0..MAX_MULTISIGS_PER_CREATOR {
+			assert_ok!(Multisig::create_multisig(
+				RuntimeOrigin::signed(creator),
+				members.clone(),
+				Some(2)
+			));
+		}
+		assert_eq!(
+			Multisig::multisigs_by_creator(&creator),
+			(0..MAX_MULTISIGS_PER_CREATOR as u64)
+				.map(Multisig::generate_multi_account_id)
+				.collect::<Vec<_>>()
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(creator), members.clone(), Some(2)),
+			Error::<Test>::TooManyMultisigs
+		);
+	});
+}
+
+#[test]
+fn delete_multisig_frees_creator_slot() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(1)
+		));
+		assert_eq!(Multisig::multisigs_by_creator(&creator), vec![multisig_id]);
+		assert_ok!(Multisig::delete_multisig(RuntimeOrigin::signed(multisig_id), multisig_id));
+		assert!(Multisig::multisigs_by_creator(&creator).is_empty());
+	});
+}
+
+#[test]
+fn dispatch_only_calls_require_multisig_origin() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(3, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		// A single member can not bypass the threshold by calling them directly
+		assert_noop!(
+			Multisig::cancel_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Multisig::delete_multisig(RuntimeOrigin::signed(creator), multisig_id),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn fund_and_propose_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let amount: u128 = 1_000u128.into();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, amount);
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::fund_and_propose(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			amount,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		assert!(Transactions::<Test>::get(&multisig_id, &transaction_id).is_some());
+		assert_eq!(Balances::free_balance(&multisig_id), amount.saturating_add(1u32.into()));
+		System::assert_has_event(
+			Event::MultisigFunded { from: creator, to: multisig_id, amount }.into(),
+		);
+		System::assert_last_event(
+			Event::TransactionCreated {
+				proposer: creator,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				status: TransactionStatus::Pending,
+				call_hash,
+				attachments: BoundedVec::default(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn fund_and_propose_non_member() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		Balances::set_balance(&10, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_noop!(
+			Multisig::fund_and_propose(
+				RuntimeOrigin::signed(10),
+				multisig_id,
+				1_000u128.into(),
+				call_transfer(3, 1_000u128.into()),
+			),
+			Error::<Test>::ProposerMustBeMember
+		);
+	});
+}
+
+#[test]
+fn fund_multisig_below_minimum() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_noop!(
+			Multisig::fund_multisig(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				MIN_FUNDING_AMOUNT - 1
+			),
+			Error::<Test>::BelowMinimumFunding
+		);
+		assert_ok!(Multisig::fund_multisig(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			MIN_FUNDING_AMOUNT
+		));
+	});
+}
+
+#[test]
+fn set_min_funding_amount_overrides_default() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let min_amount: u128 = 500u128.into();
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		// A single member can not change it without a passed proposal
+		assert_noop!(
+			Multisig::set_min_funding_amount(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				Some(min_amount)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Multisig::set_min_funding_amount(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Some(min_amount)
+		));
+		System::assert_last_event(
+			Event::MinFundingAmountSet { multisig: multisig_id, amount: Some(min_amount) }.into(),
+		);
+		assert_eq!(Multisig::min_funding_amount(&multisig_id), min_amount);
+		assert_noop!(
+			Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, min_amount - 1),
+			Error::<Test>::BelowMinimumFunding
+		);
+		// Resetting the override falls back to the default minimum
+		assert_ok!(Multisig::set_min_funding_amount(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			None
+		));
+		assert_eq!(Multisig::min_funding_amount(&multisig_id), MIN_FUNDING_AMOUNT);
+	});
+}
+
+#[test]
+fn proposal_digest_is_domain_separated() {
+	new_test_ext().execute_with(|| {
+		let call_hash = blake2_256(&call_transfer(3, 1_000u128.into()).encode());
+		let digest = Multisig::proposal_digest(&1, 0, call_hash, 100);
+		let expected =
+			blake2_256(&(MultisigPalletId::get(), 1u64, 0u64, call_hash, 100u64).encode());
+		assert_eq!(digest, expected);
+		// Any change to the signed fields produces a different digest
+		assert_ne!(digest, Multisig::proposal_digest(&2, 0, call_hash, 100));
+		assert_ne!(digest, Multisig::proposal_digest(&1, 1, call_hash, 100));
+		assert_ne!(digest, Multisig::proposal_digest(&1, 0, call_hash, 101));
+	});
+}
+
+#[test]
+fn approve_with_signature_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let signer = 2;
+		let relayer = 10;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		let digest = Multisig::proposal_digest(
+			&multisig_id,
+			transaction.nonce,
+			transaction.call_hash,
+			transaction.expires_at,
+		);
+		assert_ok!(Multisig::approve_with_signature(
+			RuntimeOrigin::signed(relayer),
+			multisig_id,
+			transaction_id,
+			signer,
+			TestSignature(signer, Multisig::approval_payload(digest)),
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.votes.get(&signer), Some(&(Vote::Approve, 1)));
+		System::assert_has_event(
+			Event::TransactionVoted {
+				voter: signer,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				vote: Vote::Approve,
+				call_hash,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn approve_with_signature_rejects_invalid_signature() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let signer = 2;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		// Signed over a different nonce than the stored proposal
+		let digest = Multisig::proposal_digest(
+			&multisig_id,
+			transaction.nonce + 1,
+			transaction.call_hash,
+			transaction.expires_at,
+		);
+		assert_noop!(
+			Multisig::approve_with_signature(
+				RuntimeOrigin::signed(10),
+				multisig_id,
+				transaction_id,
+				signer,
+				TestSignature(signer, Multisig::approval_payload(digest)),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed by a different account than the claimed signer
+		let digest = Multisig::proposal_digest(
+			&multisig_id,
+			transaction.nonce,
+			transaction.call_hash,
+			transaction.expires_at,
+		);
+		assert_noop!(
+			Multisig::approve_with_signature(
+				RuntimeOrigin::signed(10),
+				multisig_id,
+				transaction_id,
+				signer,
+				TestSignature(3, Multisig::approval_payload(digest)),
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn approve_with_signature_rejects_other_chains() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let signer = 2;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		let digest = Multisig::proposal_digest(
+			&multisig_id,
+			transaction.nonce,
+			transaction.call_hash,
+			transaction.expires_at,
+		);
+		let genesis_hash = System::block_hash(0);
+		let spec_version = <Test as frame_system::Config>::Version::get().spec_version;
+		assert_eq!(
+			Multisig::approval_payload(digest),
+			(genesis_hash, spec_version, digest).encode()
+		);
+		// Signed for a chain with a different genesis
+		let other_genesis = (H256::repeat_byte(1), spec_version, digest).encode();
+		assert_noop!(
+			Multisig::approve_with_signature(
+				RuntimeOrigin::signed(10),
+				multisig_id,
+				transaction_id,
+				signer,
+				TestSignature(signer, other_genesis),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed for a different runtime version
+		let other_version = (genesis_hash, spec_version + 1, digest).encode();
+		assert_noop!(
+			Multisig::approve_with_signature(
+				RuntimeOrigin::signed(10),
+				multisig_id,
+				transaction_id,
+				signer,
+				TestSignature(signer, other_version),
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn submit_rejected_transaction_does_not_dispatch() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let to = 4;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let amount: u128 = 1_000u128.into();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(to, amount);
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call.clone(),
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Reject
+		));
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(3),
+			multisig_id,
+			transaction_id,
+			Vote::Reject
+		));
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert!(Transactions::<Test>::get(&multisig_id, &transaction_id).is_none());
+		assert_eq!(Balances::free_balance(&to), 0);
+		System::assert_last_event(
+			Event::TransactionRejected {
+				submitter: creator,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals: 1,
+				rejections: 2,
+				status: TransactionStatus::Rejected,
+				call_hash,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn delete_multisig_cancels_pending_transactions() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		assert_ok!(Multisig::delete_multisig(RuntimeOrigin::signed(multisig_id), multisig_id));
+		assert!(Transactions::<Test>::get(&multisig_id, &transaction_id).is_none());
+		assert!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id).is_none());
+		System::assert_has_event(
+			Event::TransactionCanceled {
+				submitter: multisig_id,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				status: TransactionStatus::Canceled,
+				call_hash,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::MultisigDeleted { from: multisig_id, multisig: multisig_id }.into(),
+		);
+	});
+}
+
+#[test]
+fn tally_votes_uses_vote_weights() {
+	new_test_ext().execute_with(|| {
+		let status = TransactionStatus::Pending;
+		let mut votes = BoundedBTreeMap::<
+			<Test as frame_system::Config>::AccountId,
+			Vote,
+			<Test as Config>::MaxMembers,
+		>::new();
+		votes.try_insert(1, Vote::Reject).unwrap();
+		votes.try_insert(2, Vote::Reject).unwrap();
+		votes.try_insert(HEAVY_MEMBER, Vote::Approve).unwrap();
+		let (approvals, rejections) = Multisig::do_tally_votes(status, votes).unwrap();
+		assert_eq!(approvals, HEAVY_MEMBER_WEIGHT);
+		assert_eq!(rejections, 2);
+	});
+}
+
+#[test]
+fn weighted_vote_reaches_threshold() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members_set: BTreeSet<u64> = vec![1, 2, HEAVY_MEMBER].into_iter().collect();
+		let members =
+			BoundedBTreeSet::try_from(members_set).expect("Should have a valid members set");
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		// A single heavy vote is enough to reach the threshold
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(HEAVY_MEMBER),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		System::assert_last_event(
+			Event::ThresholdReached {
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals: 1 + HEAVY_MEMBER_WEIGHT,
+				approved_at: System::block_number(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn vote_updates_cached_tally() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members_set: BTreeSet<u64> = vec![1, 2, 3, HEAVY_MEMBER].into_iter().collect();
+		let members =
+			BoundedBTreeSet::try_from(members_set).expect("Should have a valid members set");
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let call = call_transfer(3, 1_000u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.tally, Tally { approvals: 1, rejections: 0 });
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Reject
+		));
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(HEAVY_MEMBER),
+			multisig_id,
+			transaction_id,
+			Vote::Reject
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.tally, Tally { approvals: 1, rejections: 1 + HEAVY_MEMBER_WEIGHT });
+		assert_ok!(Multisig::do_try_state());
+		// The votes already cast keep the weight their voter had when casting them
+		HeavyMemberWeight::set(1);
+		assert_ok!(Multisig::do_try_state());
+		// A cached tally out of step with the votes is caught
+		Transactions::<Test>::mutate(&multisig_id, &transaction_id, |transaction| {
+			transaction.as_mut().expect("Transaction should exist").tally.approvals = 3;
+		});
+		assert!(Multisig::do_try_state().is_err());
+		// With the tally restored the rejection threshold is met
+		Transactions::<Test>::mutate(&multisig_id, &transaction_id, |transaction| {
+			transaction.as_mut().expect("Transaction should exist").tally.approvals = 1;
+		});
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert!(!Transactions::<Test>::contains_key(&multisig_id, &transaction_id));
+		System::assert_has_event(
+			Event::TransactionRejected {
+				submitter: creator,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				approvals: 1,
+				rejections: 1 + HEAVY_MEMBER_WEIGHT,
+				status: TransactionStatus::Rejected,
+				call_hash,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn earmark_funds_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let purpose: EarmarkPurpose<Test> = BoundedVec::try_from(b"payroll".to_vec()).unwrap();
+		let amount: u128 = 500u128.into();
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_ok!(Multisig::fund_multisig(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			1_000u128.into()
+		));
+		// A single member can not lock the funds of the multisig without a passed proposal
+		assert_noop!(
+			Multisig::earmark_funds(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				purpose.clone(),
+				amount
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Multisig::earmark_funds(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			purpose.clone(),
+			amount
+		));
+		let reason: RuntimeHoldReason = HoldReason::Earmark.into();
+		assert_eq!(Balances::balance_on_hold(&reason, &multisig_id), amount);
+		assert_eq!(Multisig::earmarks(&multisig_id), vec![(purpose.clone(), amount)]);
+		System::assert_last_event(
+			Event::FundsEarmarked { multisig: multisig_id, purpose: purpose.clone(), amount }
+				.into(),
+		);
+
+		assert_noop!(
+			Multisig::release_earmark(RuntimeOrigin::signed(creator), multisig_id, purpose.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Multisig::release_earmark(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			purpose.clone()
+		));
+		assert_eq!(Balances::balance_on_hold(&reason, &multisig_id), 0);
+		assert!(Multisig::earmarks(&multisig_id).is_empty());
+		System::assert_last_event(
+			Event::EarmarkReleased { multisig: multisig_id, purpose, amount }.into(),
+		);
+		assert_eq!(EarmarkCount::<Test>::get(&multisig_id), 0);
+	});
+}
+
+#[test]
+fn earmark_funds_limits_purposes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			generate_members(),
+			Some(2)
+		));
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		let purposes: Vec<EarmarkPurpose<Test>> = (0..=MAX_EARMARKS as u8)
+			.map(|i| BoundedVec::try_from(vec![i]).unwrap())
+			.collect();
+		for purpose in &purposes[..MAX_EARMARKS as usize] {
+			assert_ok!(Multisig::earmark_funds(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				purpose.clone(),
+				100
+			));
+		}
+		assert_noop!(
+			Multisig::earmark_funds(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				purposes[MAX_EARMARKS as usize].clone(),
+				100
+			),
+			Error::<Test>::TooManyEarmarks
+		);
+		// Adding to an existing earmark does not take up another purpose
+		assert_ok!(Multisig::earmark_funds(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			purposes[0].clone(),
+			100
+		));
+		assert_ok!(Multisig::release_earmark(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			purposes[0].clone()
+		));
+		assert_ok!(Multisig::earmark_funds(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			purposes[MAX_EARMARKS as usize].clone(),
+			100
+		));
+		assert_eq!(EarmarkCount::<Test>::get(&multisig_id), MAX_EARMARKS);
+	});
+}
+
+#[test]
+fn earmark_funds_not_enough_funds() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members = generate_members();
+		let nonce = MultisigNonce::<Test>::get();
+		let multisig_id = Multisig::generate_multi_account_id(nonce);
+		let purpose: EarmarkPurpose<Test> = BoundedVec::try_from(b"payroll".to_vec()).unwrap();
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			members.clone(),
+			Some(2)
+		));
+		assert_noop!(
+			Multisig::earmark_funds(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				purpose.clone(),
+				1_000u128.into()
+			),
+			Error::<Test>::NotEnoughFunds
+		);
+		assert_noop!(
+			Multisig::release_earmark(RuntimeOrigin::signed(multisig_id), multisig_id, purpose),
+			Error::<Test>::EarmarkDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn propose_transaction_with_attachments_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(creator, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		let attachments: BoundedVec<[u8; 32], _> =
+			vec![blake2_256(b"invoice"), blake2_256(b"contract")].try_into().unwrap();
+		assert_ok!(Multisig::propose_transaction_with_options(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+			ProposalOptions { attachments: attachments.clone(), ..Default::default() },
+		));
+		let transaction_id = Multisig::generate_transaction_id(
+			multisig_id,
+			creator,
+			System::block_number(),
+			call_hash,
+		);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.attachments, attachments);
+		System::assert_last_event(
+			Event::TransactionCreated {
+				proposer: creator,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				status: TransactionStatus::Pending,
+				call_hash,
+				attachments,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn propose_transaction_invalid_execution_window() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(creator, 100u128.into());
+		// The window must not be empty
+		assert_noop!(
+			Multisig::propose_transaction_with_options(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				call.clone(),
+				ProposalOptions {
+					execute_not_before: Some(10),
+					execute_not_after: Some(5),
+					..Default::default()
+				},
+			),
+			Error::<Test>::InvalidExecutionWindow
+		);
+		// The window must close before the transaction expires
+		assert_noop!(
+			Multisig::propose_transaction_with_options(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				call,
+				ProposalOptions {
+					execute_not_after: Some(DEFAULT_EXPIRATION_BLOCKS + 2),
+					..Default::default()
+				},
+			),
+			Error::<Test>::InvalidExecutionWindow
+		);
+	});
+}
+
+#[test]
+fn submit_transaction_within_execution_window() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let to = 2;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		let call = call_transfer(to, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction_with_options(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call.clone(),
+			ProposalOptions {
+				execute_not_before: Some(5),
+				execute_not_after: Some(10),
+				..Default::default()
+			},
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				call_hash
+			),
+			Error::<Test>::ExecutionWindowNotOpen
+		);
+		System::set_block_number(11);
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				call_hash
+			),
+			Error::<Test>::ExecutionWindowClosed
+		);
+		System::set_block_number(5);
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert!(Transactions::<Test>::get(&multisig_id, &transaction_id).is_none());
+		assert_eq!(Balances::free_balance(&to), 100);
+	});
+}
+
+#[test]
+fn lifecycle_calls_works() {
+	new_test_ext().execute_with(|| {
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		let call = call_transfer(creator, 100u128.into());
+		assert!(Multisig::lifecycle_calls(multisig_id, call.clone()).is_none());
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let calls = Multisig::lifecycle_calls(multisig_id, call).expect("Multisig should exist");
+		// The proposal, one vote on top of the proposer's own and the submission
+		assert_eq!(calls.len(), 3);
+		assert!(matches!(calls[0], crate::Call::propose_transaction { .. }));
+		assert!(matches!(calls[1], crate::Call::vote { vote: Vote::Approve, .. }));
+		assert!(matches!(calls[2], crate::Call::submit_transaction { .. }));
+	});
+}
+
+#[test]
+fn propose_signal_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let voter = 2;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let message: BoundedVec<u8, _> = b"We back the proposal".to_vec().try_into().unwrap();
+		assert_ok!(Multisig::propose_signal(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			message.clone()
+		));
+		let signal_id =
+			Multisig::generate_transaction_id(multisig_id, creator, 1, blake2_256(&message));
+		let signal = Signals::<Test>::get(&multisig_id, &signal_id).expect("Signal should exist");
+		assert_eq!(signal.message, message);
+		assert_eq!(signal.votes.get(&creator), Some(&(Vote::Approve, 1)));
+		// A pending signal takes up one of the pending slots of the multisig
+		assert_eq!(PendingTransactionCount::<Test>::get(&multisig_id), 1);
+		System::assert_last_event(
+			Event::SignalProposed {
+				proposer: creator,
+				signal: signal_id,
+				multisig: multisig_id,
+				message,
+			}
+			.into(),
+		);
+		// The signal can not be resolved before reaching a threshold
+		assert_noop!(
+			Multisig::resolve_signal(RuntimeOrigin::signed(creator), multisig_id, signal_id),
+			Error::<Test>::ThresholdNotReached
+		);
+		assert_ok!(Multisig::vote_signal(
+			RuntimeOrigin::signed(voter),
+			multisig_id,
+			signal_id,
+			Vote::Approve
+		));
+		assert_noop!(
+			Multisig::vote_signal(
+				RuntimeOrigin::signed(voter),
+				multisig_id,
+				signal_id,
+				Vote::Approve
+			),
+			Error::<Test>::AlreadyVoted
+		);
+		assert_ok!(Multisig::resolve_signal(RuntimeOrigin::signed(voter), multisig_id, signal_id));
+		assert!(Signals::<Test>::get(&multisig_id, &signal_id).is_none());
+		assert_eq!(PendingTransactionCount::<Test>::get(&multisig_id), 0);
+		System::assert_last_event(
+			Event::SignalPassed {
+				submitter: voter,
+				signal: signal_id,
+				multisig: multisig_id,
+				approvals: 2,
+				rejections: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn signal_resolves_against_the_threshold_it_was_proposed_under() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let voter = 2;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let message: BoundedVec<u8, _> = b"We back the proposal".to_vec().try_into().unwrap();
+		assert_ok!(Multisig::propose_signal(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			message.clone()
+		));
+		let signal_id =
+			Multisig::generate_transaction_id(multisig_id, creator, 1, blake2_256(&message));
+		let signal = Signals::<Test>::get(&multisig_id, &signal_id).expect("Signal should exist");
+		assert_eq!(signal.threshold, Threshold::Absolute(2));
+		assert_eq!(signal.total_weight, 3);
+		// Lowering the threshold afterwards does not apply to the pending signal
+		assert_ok!(Multisig::set_threshold(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Threshold::Absolute(1)
+		));
+		assert_noop!(
+			Multisig::resolve_signal(RuntimeOrigin::signed(creator), multisig_id, signal_id),
+			Error::<Test>::ThresholdNotReached
+		);
+		assert_ok!(Multisig::vote_signal(
+			RuntimeOrigin::signed(voter),
+			multisig_id,
+			signal_id,
+			Vote::Approve
+		));
+		assert_ok!(Multisig::resolve_signal(RuntimeOrigin::signed(voter), multisig_id, signal_id));
+	});
+}
+
+#[test]
+fn signal_votes_keep_the_weight_they_were_cast_with() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let members_set: BTreeSet<u64> = vec![1, 2, HEAVY_MEMBER].into_iter().collect();
+		let members =
+			BoundedBTreeSet::try_from(members_set).expect("Should have a valid members set");
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		let message: BoundedVec<u8, _> = b"We back the proposal".to_vec().try_into().unwrap();
+		assert_ok!(Multisig::propose_signal(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			message.clone()
+		));
+		let signal_id =
+			Multisig::generate_transaction_id(multisig_id, creator, 1, blake2_256(&message));
+		assert_ok!(Multisig::vote_signal(
+			RuntimeOrigin::signed(HEAVY_MEMBER),
+			multisig_id,
+			signal_id,
+			Vote::Approve
+		));
+		let signal = Signals::<Test>::get(&multisig_id, &signal_id).expect("Signal should exist");
+		assert_eq!(signal.votes.get(&HEAVY_MEMBER), Some(&(Vote::Approve, HEAVY_MEMBER_WEIGHT)));
+		// Lowering the weight of a voter afterwards does not move the tally
+		HeavyMemberWeight::set(1);
+		assert_ok!(Multisig::resolve_signal(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			signal_id
+		));
+		System::assert_last_event(
+			Event::SignalPassed {
+				submitter: creator,
+				signal: signal_id,
+				multisig: multisig_id,
+				approvals: 1 + HEAVY_MEMBER_WEIGHT,
+				rejections: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn prune_expired_signal_task_works() {
+	use frame_support::traits::Task as _;
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let message: BoundedVec<u8, _> = b"We back the proposal".to_vec().try_into().unwrap();
+		assert_ok!(Multisig::propose_signal(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			message.clone()
+		));
+		let signal_id =
+			Multisig::generate_transaction_id(multisig_id, creator, 1, blake2_256(&message));
+		let task = Task::<Test>::PruneExpiredSignal { multisig_id, signal_id };
+		// Nothing can be pruned before the signal expires
+		System::set_block_number(1 + DEFAULT_EXPIRATION_BLOCKS);
+		assert!(!task.is_valid());
+		assert_eq!(Task::<Test>::iter().count(), 0);
+		assert_noop!(task.run(), Error::<Test>::SignalNotExpired);
+		System::set_block_number(2 + DEFAULT_EXPIRATION_BLOCKS);
+		// An expired signal can no longer be voted on or resolved
+		assert_noop!(
+			Multisig::vote_signal(RuntimeOrigin::signed(2), multisig_id, signal_id, Vote::Approve),
+			Error::<Test>::SignalExpired
+		);
+		assert_noop!(
+			Multisig::resolve_signal(RuntimeOrigin::signed(creator), multisig_id, signal_id),
+			Error::<Test>::SignalExpired
+		);
+		assert!(task.is_valid());
+		assert_eq!(Task::<Test>::iter().collect::<Vec<_>>(), vec![task.clone()]);
+		assert_ok!(task.run());
+		assert!(Signals::<Test>::get(&multisig_id, &signal_id).is_none());
+		assert_eq!(PendingTransactionCount::<Test>::get(&multisig_id), 0);
+		System::assert_last_event(
+			Event::SignalExpired { signal: signal_id, multisig: multisig_id }.into(),
+		);
+	});
+}
+
+#[test]
+fn propose_signal_non_member() {
+	new_test_ext().execute_with(|| {
+		let creator = 1;
+		let non_member = 4;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let message: BoundedVec<u8, _> = b"We back the proposal".to_vec().try_into().unwrap();
+		assert_noop!(
+			Multisig::propose_signal(RuntimeOrigin::signed(non_member), multisig_id, message),
+			Error::<Test>::ProposerMustBeMember
+		);
+	});
+}
+
+#[test]
+fn aggregate_vote_events_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		AggregateVoteEvents::set(true);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		let call = call_transfer(creator, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(3),
+			multisig_id,
+			transaction_id,
+			Vote::Reject
+		));
+		// The votes are stored but not reported individually
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.votes.len(), 3);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Multisig(Event::TransactionVoted { .. })
+		)));
+		assert_eq!(BlockVoteCounts::<Test>::get(&multisig_id, &transaction_id), 2);
+		Multisig::on_finalize(1);
+		assert_eq!(BlockVoteCounts::<Test>::iter().count(), 0);
+		System::assert_last_event(
+			Event::VotesRecorded { multisig: multisig_id, transaction: transaction_id, count: 2 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn aggregate_vote_events_limits_transactions() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		AggregateVoteEvents::set(true);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		let mut transaction_ids = Vec::new();
+		for amount in [100u128, 200u128] {
+			let call = call_transfer(creator, amount.into());
+			let call_hash = blake2_256(&call.encode());
+			assert_ok!(Multisig::propose_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				call
+			));
+			transaction_ids.push(Multisig::generate_transaction_id(
+				multisig_id,
+				creator,
+				1,
+				call_hash,
+			));
+		}
+		for transaction_id in transaction_ids.iter() {
+			assert_ok!(Multisig::vote(
+				RuntimeOrigin::signed(2),
+				multisig_id,
+				*transaction_id,
+				Vote::Approve
+			));
+		}
+		// Only the votes on the first transaction are aggregated, the limit being reached
+		assert_eq!(AggregatedTransactionCount::<Test>::get(), MAX_AGGREGATED_TRANSACTIONS);
+		assert_eq!(BlockVoteCounts::<Test>::get(&multisig_id, &transaction_ids[0]), 1);
+		assert!(!BlockVoteCounts::<Test>::contains_key(&multisig_id, &transaction_ids[1]));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Multisig(Event::TransactionVoted { transaction, .. })
+				if transaction == transaction_ids[1]
+		)));
+		Multisig::on_finalize(1);
+		assert_eq!(AggregatedTransactionCount::<Test>::get(), 0);
+		assert_eq!(BlockVoteCounts::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn vote_as_delegate_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		let member = 2;
+		let delegate = 5;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(3)));
+		assert_ok!(Multisig::set_vote_delegate(
+			RuntimeOrigin::signed(member),
+			multisig_id,
+			Some(delegate)
+		));
+		System::assert_last_event(
+			Event::VoteDelegateSet { multisig: multisig_id, member, delegate: Some(delegate) }
+				.into(),
+		);
+		let call = call_transfer(creator, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		// Only the delegate of the member can vote on their behalf
+		assert_noop!(
+			Multisig::vote_as_delegate(
+				RuntimeOrigin::signed(3),
+				multisig_id,
+				member,
+				transaction_id,
+				Vote::Approve
+			),
+			Error::<Test>::NotAVoteDelegate
+		);
+		assert_ok!(Multisig::vote_as_delegate(
+			RuntimeOrigin::signed(delegate),
+			multisig_id,
+			member,
+			transaction_id,
+			Vote::Approve
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.votes.get(&member), Some(&(Vote::Approve, 1)));
+		assert_eq!(transaction.votes.get(&delegate), None);
+		System::assert_last_event(
+			Event::TransactionVoted {
+				voter: member,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				vote: Vote::Approve,
+				call_hash,
+			}
+			.into(),
+		);
+		// The delegate can be removed by the member
+		assert_ok!(Multisig::set_vote_delegate(RuntimeOrigin::signed(member), multisig_id, None));
+		assert!(VoteDelegates::<Test>::get(&multisig_id, &member).is_none());
+	});
+}
+
+#[test]
+fn set_vote_delegate_non_member() {
+	new_test_ext().execute_with(|| {
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		assert_noop!(
+			Multisig::set_vote_delegate(RuntimeOrigin::signed(5), multisig_id, Some(6)),
+			Error::<Test>::NotAMember
+		);
+	});
+}
+
+#[test]
+fn submit_transaction_mismatching_call_hash() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		// The expected hash must match the hash of the stored call, not just any call
+		let other_call_hash = blake2_256(&call_transfer(3, 100u128.into()).encode());
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				other_call_hash
+			),
+			Error::<Test>::MismatchingCallHash
+		);
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id).is_none());
+	});
+}
+
+#[test]
+fn activity_history_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		System::set_block_number(2);
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 100));
+		let call = call_transfer(creator, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 2, call_hash);
+		System::set_block_number(3);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert_eq!(
+			Multisig::activity(&multisig_id, 2),
+			vec![
+				ActivityEntry {
+					block: 2,
+					activity: Activity::Funded { from: creator, amount: 100 }
+				},
+				ActivityEntry {
+					block: 2,
+					activity: Activity::Proposed { proposer: creator, transaction: transaction_id },
+				},
+				ActivityEntry {
+					block: 3,
+					activity: Activity::Voted {
+						voter: 2,
+						transaction: transaction_id,
+						vote: Vote::Approve
+					},
+				},
+				ActivityEntry {
+					block: 3,
+					activity: Activity::Executed { submitter: 2, transaction: transaction_id },
+				},
+			]
+		);
+		// The oldest entry is dropped once the history is full
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 100));
+		let history = Multisig::activity(&multisig_id, 0);
+		assert_eq!(history.len() as u32, MAX_HISTORY_LENGTH);
+		assert_eq!(history[0].activity, Activity::Funded { from: creator, amount: 100 });
+		assert_eq!(history[4].activity, Activity::Funded { from: creator, amount: 100 });
+	});
+}
+
+#[test]
+fn identical_proposals_on_different_multisigs_do_not_collide() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		// Two multisigs sharing the same signers
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			generate_members(),
+			Some(2)
+		));
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			generate_members(),
+			Some(2)
+		));
+		let first_multisig_id = Multisig::generate_multi_account_id(0);
+		let second_multisig_id = Multisig::generate_multi_account_id(1);
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		// The same proposer proposes the identical call on both in the same block
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			first_multisig_id,
+			call.clone()
+		));
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			second_multisig_id,
+			call
+		));
+		let first_transaction_id =
+			Multisig::generate_transaction_id(first_multisig_id, creator, 1, call_hash);
+		let second_transaction_id =
+			Multisig::generate_transaction_id(second_multisig_id, creator, 1, call_hash);
+		assert_ne!(first_transaction_id, second_transaction_id);
+		assert!(Transactions::<Test>::contains_key(&first_multisig_id, &first_transaction_id));
+		assert!(Transactions::<Test>::contains_key(&second_multisig_id, &second_transaction_id));
+		assert!(!Transactions::<Test>::contains_key(&first_multisig_id, &second_transaction_id));
+	});
+}
+
+#[test]
+fn create_multisigs_best_effort_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let not_a_member: BoundedBTreeSet<u64, _> =
+			BoundedBTreeSet::try_from(BTreeSet::from([2, 3])).unwrap();
+		let multisigs: BoundedVec<_, _> = vec![
+			(generate_members(), Some(2)),
+			(not_a_member, Some(2)),
+			(generate_members(), Some(3)),
+		]
+		.try_into()
+		.unwrap();
+		assert_ok!(Multisig::create_multisigs(
+			RuntimeOrigin::signed(creator),
+			multisigs,
+			BatchMode::BestEffort
+		));
+		// The failing item does not consume a nonce
+		let first_multisig_id = Multisig::generate_multi_account_id(0);
+		let second_multisig_id = Multisig::generate_multi_account_id(1);
+		assert_eq!(
+			Multisigs::<Test>::get(&first_multisig_id).map(|m| m.threshold),
+			Some(Threshold::Absolute(2))
+		);
+		assert_eq!(
+			Multisigs::<Test>::get(&second_multisig_id).map(|m| m.threshold),
+			Some(Threshold::Absolute(3))
+		);
+		assert_eq!(MultisigNonce::<Test>::get(), 2);
+		System::assert_has_event(
+			Event::NewMultisig { creator, multisig: second_multisig_id }.into(),
+		);
+		System::assert_has_event(
+			Event::MultisigCreationFailed {
+				creator,
+				index: 1,
+				error: Error::<Test>::ProposerMustBeMember.into(),
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::MultisigsBatchCreated { creator, created: 2, failed: 1 }.into(),
+		);
+	});
+}
+
+#[test]
+fn create_multisigs_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let multisigs: BoundedVec<_, _> =
+			vec![(generate_members(), Some(2)), (generate_members(), Some(4))]
+				.try_into()
+				.unwrap();
+		assert_noop!(
+			Multisig::create_multisigs(
+				RuntimeOrigin::signed(creator),
+				multisigs,
+				BatchMode::AllOrNothing
+			),
+			Error::<Test>::ThresholdTooHigh
+		);
+	});
+}
+
+#[test]
+fn set_threshold_keeps_pending_transactions_consistent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let to = 2;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		let call = call_transfer(to, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let pending_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		// Members cannot change the threshold without a proposal
+		assert_noop!(
+			Multisig::set_threshold(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				Threshold::Absolute(3)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Multisig::set_threshold(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				Threshold::Absolute(4)
+			),
+			Error::<Test>::ThresholdTooHigh
+		);
+		// Neither kind of threshold can require no votes
+		assert_noop!(
+			Multisig::set_threshold(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				Threshold::Absolute(0)
+			),
+			Error::<Test>::ThresholdTooLow
+		);
+		assert_noop!(
+			Multisig::set_threshold(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				Threshold::Percentage(Percent::from_percent(0))
+			),
+			Error::<Test>::ThresholdTooLow
+		);
+		assert_ok!(Multisig::set_threshold(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Threshold::Percentage(Percent::from_percent(50))
+		));
+		System::assert_last_event(
+			Event::ThresholdChanged {
+				multisig: multisig_id,
+				threshold: Threshold::Percentage(Percent::from_percent(50)),
+			}
+			.into(),
+		);
+		// A new proposal needs half of the three members, rounded up
+		System::set_block_number(2);
+		let new_call = call_transfer(to, 50u128.into());
+		let new_call_hash = blake2_256(&new_call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			new_call
+		));
+		let new_id = Multisig::generate_transaction_id(multisig_id, creator, 2, new_call_hash);
+		let new_transaction =
+			Transactions::<Test>::get(&multisig_id, &new_id).expect("Transaction should exist");
+		assert_eq!(new_transaction.threshold, Threshold::Percentage(Percent::from_percent(50)));
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			new_id,
+			new_call_hash
+		));
+		assert!(Transactions::<Test>::get(&multisig_id, &new_id).is_some());
+		// The pending proposal is still evaluated against its absolute threshold of one
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			pending_id,
+			call_hash
+		));
+		assert!(Transactions::<Test>::get(&multisig_id, &pending_id).is_none());
+		assert_eq!(Balances::free_balance(&to), 100);
+	});
+}
+
+#[test]
+fn percentage_threshold_counts_vote_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members_set: BTreeSet<u64> = vec![1, 2, 3, HEAVY_MEMBER].into_iter().collect();
+		let members =
+			BoundedBTreeSet::try_from(members_set).expect("Should have a valid members set");
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::set_threshold(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Threshold::Percentage(Percent::from_percent(50))
+		));
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		let total_weight = 3 + HEAVY_MEMBER_WEIGHT;
+		assert_eq!(transaction.total_weight, total_weight);
+		// Half of the combined weight is more than half of the members
+		assert_eq!(transaction.threshold.votes_required(total_weight), 3);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.approved_at, None);
+		assert_ok!(Multisig::vote(
+			RuntimeOrigin::signed(3),
+			multisig_id,
+			transaction_id,
+			Vote::Approve
+		));
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.approved_at, Some(1));
+	});
+}
+
+#[test]
+fn migrate_v0_to_v1_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		let orphan_id = Multisig::generate_multi_account_id(1);
+		let transaction_id = H256::repeat_byte(1);
+		migrations::v0::Multisigs::<Test>::insert(
+			&multisig_id,
+			migrations::v0::MultisigAccount {
+				creator: 1,
+				members: generate_members(),
+				threshold: 2,
+				created_at: 1,
+			},
+		);
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		let mut votes = BoundedBTreeMap::new();
+		votes.try_insert(1, Vote::Approve).unwrap();
+		votes.try_insert(2, Vote::Approve).unwrap();
+		let old_transaction = migrations::v0::Transaction {
+			proposer: 1,
+			status: TransactionStatus::Pending,
+			call: call.clone(),
+			call_hash,
+			votes,
+			created_at: 1,
+			expires_at: 101,
+		};
+		migrations::v0::Transactions::<Test>::insert(
+			&multisig_id,
+			&transaction_id,
+			&old_transaction,
+		);
+		// A transaction left behind by a deleted multisig
+		migrations::v0::Transactions::<Test>::insert(&orphan_id, &transaction_id, old_transaction);
+		assert_eq!(Multisig::on_chain_storage_version(), 0);
+		migrations::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 1);
+		let multisig = Multisigs::<Test>::get(&multisig_id).expect("Multisig should exist");
+		assert_eq!(multisig.threshold, Threshold::Absolute(2));
+		assert!(Members::<Test>::contains_key(&multisig_id, 3));
+		assert_eq!(MultisigsByCreator::<Test>::get(1).into_inner(), vec![multisig_id]);
+		let transaction = Transactions::<Test>::get(&multisig_id, &transaction_id)
+			.expect("Transaction should exist");
+		assert_eq!(transaction.threshold, Threshold::Absolute(2));
+		assert_eq!(transaction.total_weight, 3);
+		assert_eq!(transaction.tally, Tally { approvals: 2, rejections: 0 });
+		assert_eq!(transaction.approved_at, Some(10));
+		assert_eq!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id), Some(call));
+		assert!(CallLocations::<Test>::contains_key(call_hash, (multisig_id, transaction_id)));
+		assert_eq!(PendingTransactionCount::<Test>::get(&multisig_id), 1);
+		assert!(Transactions::<Test>::get(&orphan_id, &transaction_id).is_none());
+		assert_ok!(Multisig::do_try_state());
+	});
+}
+
+#[test]
+fn contribute_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let contributor = 5;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		Balances::set_balance(&contributor, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		assert_noop!(
+			Multisig::contribute(RuntimeOrigin::signed(contributor), multisig_id, 0),
+			Error::<Test>::ZeroAmount
+		);
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(contributor), multisig_id, 1_000));
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(contributor), multisig_id, 500));
+		assert_eq!(Contributions::<Test>::get(&multisig_id, &contributor), 1_500);
+		assert_eq!(
+			Balances::balance_on_hold(&HoldReason::Contribution.into(), &multisig_id),
+			1_500
+		);
+		System::assert_last_event(
+			Event::ContributionMade { from: contributor, multisig: multisig_id, amount: 500 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn delete_multisig_refunds_contributions() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let (first, second, third) = (5, 6, 7);
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		Balances::set_balance(&first, 1_000_000u128.into());
+		Balances::set_balance(&second, 1_000_000u128.into());
+		Balances::set_balance(&third, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(first), multisig_id, 3_000));
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(second), multisig_id, 1_000));
+		// Existing contributors can add to their contribution past the contributor limit
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(first), multisig_id, 1_000));
+		assert_noop!(
+			Multisig::contribute(RuntimeOrigin::signed(third), multisig_id, 1_000),
+			Error::<Test>::TooManyContributors
+		);
+		assert_eq!(ContributorCount::<Test>::get(&multisig_id), MAX_CONTRIBUTORS);
+		// Plain funding is not attributed to anyone and goes to the creator
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(second), multisig_id, 500));
+		// The contributions are held, so the multisig can only spend the plain funding
+		assert_eq!(Balances::free_balance(&multisig_id), 500 + 1);
+		assert_eq!(
+			Balances::balance_on_hold(&HoldReason::Contribution.into(), &multisig_id),
+			4_000
+		);
+		let creator_balance = Balances::free_balance(&creator);
+		assert_ok!(Multisig::delete_multisig(RuntimeOrigin::signed(multisig_id), multisig_id));
+		assert_eq!(Balances::free_balance(&first), 1_000_000);
+		assert_eq!(Balances::free_balance(&second), 1_000_000 - 500);
+		System::assert_has_event(
+			Event::ContributionRefunded { to: first, multisig: multisig_id, amount: 4_000 }.into(),
+		);
+		// The creator gets the plain funding back along with the deposit and its transfer fee
+		assert_eq!(Balances::free_balance(&creator), creator_balance + 500 + MULTISIG_DEPOSIT + 1);
+		assert_eq!(Contributions::<Test>::iter_prefix(&multisig_id).count(), 0);
+		assert_eq!(ContributorCount::<Test>::get(&multisig_id), 0);
+		assert_eq!(Balances::total_balance(&multisig_id), 0);
+	});
+}
+
+#[test]
+fn heartbeat_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		assert_noop!(
+			Multisig::heartbeat(RuntimeOrigin::signed(5), multisig_id),
+			Error::<Test>::NotAMember
+		);
+		assert_ok!(Multisig::heartbeat(RuntimeOrigin::signed(creator), multisig_id));
+		System::assert_last_event(
+			Event::HeartbeatReceived { multisig: multisig_id, member: creator }.into(),
+		);
+		System::set_block_number(5);
+		assert_ok!(Multisig::heartbeat(RuntimeOrigin::signed(2), multisig_id));
+		assert_eq!(Multisig::liveness(&multisig_id), vec![(1, Some(1)), (2, Some(5)), (3, None)]);
+	});
+}
+
+#[test]
+fn multisig_builder_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let multisig_id = test_utils::MultisigBuilder::<Test>::new()
+			.members([1, 2, 3])
+			.threshold(2)
+			.funded(1_000)
+			.build();
+		let multisig = Multisigs::<Test>::get(&multisig_id).expect("Multisig should exist");
+		assert_eq!(multisig.creator, 1);
+		assert_eq!(multisig.members, generate_members());
+		assert_eq!(multisig.threshold, Threshold::Absolute(2));
+		assert!(Members::<Test>::contains_key(&multisig_id, &3));
+		assert_eq!(Multisig::multisigs_by_creator(&1), vec![multisig_id]);
+		assert_eq!(Balances::free_balance(&multisig_id), 1_000);
+		// The built multisig can be used like any other
+		assert_ok!(Multisig::heartbeat(RuntimeOrigin::signed(2), multisig_id));
+		assert_eq!(Multisig::next_multisig_id(), Multisig::generate_multi_account_id(1));
+	});
+}
+
+#[test]
+#[should_panic(expected = "the creator must be a member")]
+fn multisig_builder_rejects_non_member_creator() {
+	new_test_ext().execute_with(|| {
+		test_utils::MultisigBuilder::<Test>::new().creator(4).members([1, 2, 3]).build();
+	});
+}
+
+#[test]
+#[should_panic(expected = "the threshold is higher than the combined vote weight of the members")]
+fn multisig_builder_rejects_unreachable_threshold() {
+	new_test_ext().execute_with(|| {
+		test_utils::MultisigBuilder::<Test>::new()
+			.members([1, 2, 3])
+			.threshold(4)
+			.build();
+	});
+}
+
+#[test]
+fn category_transaction_dispatches_from_derived_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		let call = Box::new(RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"upgrade".to_vec(),
+		}));
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction_with_options(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call,
+			ProposalOptions { category: Some(*b"upgrades"), ..Default::default() },
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert_eq!(
+			TransactionCategories::<Test>::get(&multisig_id, &transaction_id),
+			Some(*b"upgrades")
+		);
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		let category_account = Multisig::category_account_id(&multisig_id, b"upgrades");
+		assert_ne!(category_account, multisig_id);
+		assert_ne!(category_account, Multisig::category_account_id(&multisig_id, b"treasury"));
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: category_account,
+				hash: blake2_256(b"upgrade").into(),
+			}
+			.into(),
+		);
+		assert!(TransactionCategories::<Test>::get(&multisig_id, &transaction_id).is_none());
+	});
+}
+
+#[test]
+fn prune_expired_transaction_task_works() {
+	use frame_support::traits::Task as _;
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		let task = Task::<Test>::PruneExpiredTransaction { multisig_id, transaction_id };
+		// Nothing can be pruned before the transaction expires
+		System::set_block_number(1 + DEFAULT_EXPIRATION_BLOCKS);
+		assert!(!task.is_valid());
+		assert_eq!(Task::<Test>::iter().count(), 0);
+		assert_noop!(task.run(), Error::<Test>::TransactionNotExpired);
+		System::set_block_number(2 + DEFAULT_EXPIRATION_BLOCKS);
+		// An expired transaction can no longer be voted on or submitted
+		assert_noop!(
+			Multisig::vote(RuntimeOrigin::signed(2), multisig_id, transaction_id, Vote::Approve),
+			Error::<Test>::TransactionExpired
+		);
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				call_hash
+			),
+			Error::<Test>::TransactionExpired
+		);
+		assert!(task.is_valid());
+		assert_eq!(Task::<Test>::iter().collect::<Vec<_>>(), vec![task.clone()]);
+		assert_ok!(task.run());
+		assert!(Transactions::<Test>::get(&multisig_id, &transaction_id).is_none());
+		assert!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id).is_none());
+		assert_eq!(PendingTransactionCount::<Test>::get(&multisig_id), 0);
+		System::assert_last_event(
+			Event::TransactionExpired {
+				transaction: transaction_id,
+				multisig: multisig_id,
+				status: TransactionStatus::Expired,
+				call_hash,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn private_transaction_reveal_before_execute() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let to = 2;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		let call = call_transfer(to, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose_private_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call_hash,
+			Default::default()
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert!(TransactionCalls::<Test>::get(&multisig_id, &transaction_id).is_none());
+		// The call can not be executed before being revealed
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				call_hash
+			),
+			Error::<Test>::CallNotRevealed
+		);
+		assert_noop!(
+			Multisig::reveal_call(
+				RuntimeOrigin::signed(2),
+				multisig_id,
+				transaction_id,
+				call_transfer(to, 1_000u128.into())
+			),
+			Error::<Test>::MismatchingCallHash
+		);
+		System::set_block_number(2);
+		assert_ok!(Multisig::reveal_call(
+			RuntimeOrigin::signed(2),
+			multisig_id,
+			transaction_id,
+			call.clone()
+		));
+		System::assert_last_event(
+			Event::CallRevealed {
+				revealer: 2,
+				transaction: transaction_id,
+				multisig: multisig_id,
+				call_hash,
+			}
+			.into(),
+		);
+		assert_noop!(
+			Multisig::reveal_call(RuntimeOrigin::signed(2), multisig_id, transaction_id, call),
+			Error::<Test>::CallAlreadyRevealed
+		);
+		// The members get the reveal period to verify the call
+		System::set_block_number(1 + REVEAL_PERIOD);
+		assert_noop!(
+			Multisig::submit_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				transaction_id,
+				call_hash
+			),
+			Error::<Test>::RevealPeriodNotElapsed
+		);
+		System::set_block_number(2 + REVEAL_PERIOD);
+		assert_ok!(Multisig::submit_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			transaction_id,
+			call_hash
+		));
+		assert_eq!(Balances::free_balance(&to), 100);
+		assert!(RevealedAt::<Test>::get(&multisig_id, &transaction_id).is_none());
+	});
+}
+
+#[test]
+fn set_member_label_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let label: MemberLabel<Test> = b"cfo".to_vec().try_into().unwrap();
+		assert_noop!(
+			Multisig::set_member_label(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				5,
+				Some(label.clone())
+			),
+			Error::<Test>::NotAMember
+		);
+		// A single member can not label the others without a passed proposal
+		assert_noop!(
+			Multisig::set_member_label(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				2,
+				Some(label.clone())
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Multisig::set_member_label(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			2,
+			Some(label.clone())
+		));
+		System::assert_last_event(
+			Event::MemberLabelSet { multisig: multisig_id, member: 2, label: Some(label.clone()) }
+				.into(),
+		);
+		assert_eq!(Multisig::members(&multisig_id), vec![(1, None), (2, Some(label)), (3, None)]);
+		assert_ok!(Multisig::set_member_label(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			2,
+			None
+		));
+		assert!(MemberLabels::<Test>::get(&multisig_id, &2).is_none());
+	});
+}
+
+#[test]
+fn set_max_pending_transactions_adjusts_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		// The default limit is enforced
+		for block in 1..=DEFAULT_MAX_PENDING_TRANSACTIONS as u64 {
+			System::set_block_number(block);
+			assert_ok!(Multisig::propose_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				call_transfer(2, 100u128.into())
+			));
+		}
+		assert_noop!(
+			Multisig::propose_transaction(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				call_transfer(3, 100u128.into())
+			),
+			Error::<Test>::TooManyPendingTransactions
+		);
+		// A single member can not raise the limit without a passed proposal
+		assert_noop!(
+			Multisig::set_max_pending_transactions(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				DEFAULT_MAX_PENDING_TRANSACTIONS
+			),
+			DispatchError::BadOrigin
+		);
+		// Raising the limit holds a deposit from the multisig account
+		let limit = DEFAULT_MAX_PENDING_TRANSACTIONS + 2;
+		assert_noop!(
+			Multisig::set_max_pending_transactions(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				limit
+			),
+			Error::<Test>::NotEnoughFunds
+		);
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), multisig_id, 1_000));
+		// The limit can not be raised past the configured cap
+		assert_noop!(
+			Multisig::set_max_pending_transactions(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				MAX_PENDING_TRANSACTIONS_LIMIT + 1
+			),
+			Error::<Test>::PendingLimitTooHigh
+		);
+		assert_ok!(Multisig::set_max_pending_transactions(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			limit
+		));
+		let reason = HoldReason::PendingLimitDeposit.into();
+		assert_eq!(
+			Balances::balance_on_hold(&reason, &multisig_id),
+			2 * PENDING_TRANSACTION_DEPOSIT
+		);
+		System::assert_last_event(
+			Event::MaxPendingTransactionsSet {
+				multisig: multisig_id,
+				limit,
+				deposit: 2 * PENDING_TRANSACTION_DEPOSIT,
+			}
+			.into(),
+		);
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call_transfer(3, 100u128.into())
+		));
+		assert_eq!(
+			PendingTransactionCount::<Test>::get(&multisig_id),
+			DEFAULT_MAX_PENDING_TRANSACTIONS + 1
+		);
+		// The limit can not be lowered below the transactions already pending
+		assert_noop!(
+			Multisig::set_max_pending_transactions(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				DEFAULT_MAX_PENDING_TRANSACTIONS
+			),
+			Error::<Test>::TooManyPendingTransactions
+		);
+		// Lowering the limit refunds the deposit
+		assert_ok!(Multisig::set_max_pending_transactions(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			DEFAULT_MAX_PENDING_TRANSACTIONS + 1
+		));
+		assert_eq!(Balances::balance_on_hold(&reason, &multisig_id), PENDING_TRANSACTION_DEPOSIT);
+	});
+}
+
+#[test]
+fn stored_calls_are_served_as_preimages() {
+	use frame_support::traits::{QueryPreimage, StorePreimage};
+	use preimages::StoredCallPreimages;
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(2)));
+		let call = call_transfer(2, 100u128.into());
+		let call_hash = blake2_256(&call.encode());
+		let hash = H256(call_hash);
+		assert_eq!(StoredCallPreimages::<Test>::len(&hash), None);
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call.clone()
+		));
+		let transaction_id = Multisig::generate_transaction_id(multisig_id, creator, 1, call_hash);
+		assert_eq!(StoredCallPreimages::<Test>::len(&hash), Some(call.encoded_size() as u32));
+		assert_eq!(
+			StoredCallPreimages::<Test>::fetch(&hash, None).map(|b| b.into_owned()),
+			Ok(call.encode())
+		);
+		assert_eq!(StoredCallPreimages::<Test>::note(call.encode().into()), Ok(hash));
+		// The preimage goes away with the transaction holding it
+		assert_ok!(Multisig::cancel_transaction(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			transaction_id
+		));
+		assert_eq!(StoredCallPreimages::<Test>::len(&hash), None);
+		assert!(StoredCallPreimages::<Test>::note(call.encode().into()).is_err());
+	});
+}
+
+#[test]
+fn wind_down_sweeps_to_beneficiary_after_sunset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let contributor = 5;
+		let beneficiary = 7;
+		let members = generate_members();
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		Balances::set_balance(&contributor, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(creator), members, Some(1)));
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(contributor), multisig_id, 1_000));
+		let sunset = Sunset { sunset_at: 10, beneficiary: Some(beneficiary) };
+		// Members cannot set a sunset without a proposal
+		assert_noop!(
+			Multisig::set_sunset(
+				RuntimeOrigin::signed(creator),
+				multisig_id,
+				Some(Sunset { sunset_at: 2, beneficiary: Some(creator) })
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Multisig::set_sunset(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				Some(Sunset { sunset_at: 1, beneficiary: None })
+			),
+			Error::<Test>::InvalidSunset
+		);
+		assert_ok!(Multisig::set_sunset(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Some(sunset.clone())
+		));
+		System::assert_last_event(
+			Event::SunsetSet { multisig: multisig_id, sunset: Some(sunset) }.into(),
+		);
+		let call = call_transfer(2, 100u128.into());
+		assert_ok!(Multisig::propose_transaction(
+			RuntimeOrigin::signed(creator),
+			multisig_id,
+			call.clone()
+		));
+		assert_noop!(
+			Multisig::wind_down(RuntimeOrigin::signed(creator), multisig_id),
+			Error::<Test>::SunsetNotReached
+		);
+		System::set_block_number(10);
+		assert_noop!(
+			Multisig::propose_transaction(RuntimeOrigin::signed(creator), multisig_id, call),
+			Error::<Test>::MultisigSunset
+		);
+		assert_noop!(
+			Multisig::wind_down(RuntimeOrigin::signed(contributor), multisig_id),
+			Error::<Test>::NotAMember
+		);
+		// Any member can wind the multisig down once the sunset is reached
+		assert_ok!(Multisig::wind_down(RuntimeOrigin::signed(2), multisig_id));
+		System::assert_last_event(
+			Event::MultisigWoundDown { multisig: multisig_id, to: beneficiary }.into(),
+		);
+		// The contribution is refunded before the rest is swept to the beneficiary
+		assert_eq!(Balances::free_balance(&contributor), 1_000_000);
+		assert_eq!(Balances::free_balance(&beneficiary), MULTISIG_DEPOSIT + 1);
+		assert!(!Multisigs::<Test>::contains_key(&multisig_id));
+		assert!(!Sunsets::<Test>::contains_key(&multisig_id));
+		assert_eq!(Transactions::<Test>::iter_prefix(&multisig_id).count(), 0);
+	});
+}
+
+#[test]
+fn sunset_multisigs_are_wound_down_on_idle() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		let contributor = 5;
+		let multisig_id = Multisig::generate_multi_account_id(0);
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		Balances::set_balance(&contributor, 1_000_000u128.into());
+		assert_ok!(Multisig::create_multisig(
+			RuntimeOrigin::signed(creator),
+			generate_members(),
+			Some(1)
+		));
+		assert_ok!(Multisig::contribute(RuntimeOrigin::signed(contributor), multisig_id, 1_000));
+		assert_ok!(Multisig::set_sunset(
+			RuntimeOrigin::signed(multisig_id),
+			multisig_id,
+			Some(Sunset { sunset_at: 10, beneficiary: None })
+		));
+		// Nothing is wound down before the sunset
+		Multisig::on_idle(9, Weight::MAX);
+		assert!(Multisigs::<Test>::contains_key(&multisig_id));
+		// Nor without the weight to wind the multisig down
+		System::set_block_number(10);
+		Multisig::on_idle(10, Weight::zero());
+		assert!(Multisigs::<Test>::contains_key(&multisig_id));
+		let creator_balance = Balances::free_balance(&creator);
+		Multisig::on_idle(10, Weight::MAX);
+		System::assert_last_event(
+			Event::MultisigWoundDown { multisig: multisig_id, to: creator }.into(),
+		);
+		// Without a beneficiary the contribution is refunded and the rest goes to the creator
+		assert_eq!(Balances::free_balance(&contributor), 1_000_000);
+		assert_eq!(Balances::free_balance(&creator), creator_balance + MULTISIG_DEPOSIT + 1);
+		assert!(!Multisigs::<Test>::contains_key(&multisig_id));
+		assert!(!Sunsets::<Test>::contains_key(&multisig_id));
+	});
+}
+
+#[test]
+fn sunset_wind_down_resumes_after_the_last_sunset_checked() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		let multisigs: Vec<u64> = (0..2)
+			.map(|nonce| {
+				assert_ok!(Multisig::create_multisig(
+					RuntimeOrigin::signed(creator),
+					generate_members(),
+					Some(1)
+				));
+				let multisig_id = Multisig::generate_multi_account_id(nonce);
+				assert_ok!(Multisig::set_sunset(
+					RuntimeOrigin::signed(multisig_id),
+					multisig_id,
+					Some(Sunset { sunset_at: 10, beneficiary: None })
+				));
+				multisig_id
+			})
+			.collect();
+		System::set_block_number(10);
+		// Only enough weight to wind a single multisig down per idle block
+		let wind_down = Multisig::dissolve_weight(&multisigs[0]);
+		Multisig::on_idle(10, wind_down);
+		assert_eq!(multisigs.iter().filter(|id| Multisigs::<Test>::contains_key(id)).count(), 1);
+		assert!(SunsetCursor::<Test>::get().is_some());
+		// The next idle block picks up where the previous one stopped
+		Multisig::on_idle(10, wind_down);
+		assert!(multisigs.iter().all(|id| !Multisigs::<Test>::contains_key(id)));
+		assert!(SunsetCursor::<Test>::get().is_none());
+	});
+}
+
+#[test]
+fn sunset_wind_down_skips_multisigs_too_heavy_for_the_idle_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = 1;
+		Balances::set_balance(&creator, 1_000_000u128.into());
+		for nonce in 0..2 {
+			assert_ok!(Multisig::create_multisig(
+				RuntimeOrigin::signed(creator),
+				generate_members(),
+				Some(1)
+			));
+			let multisig_id = Multisig::generate_multi_account_id(nonce);
+			assert_ok!(Multisig::set_sunset(
+				RuntimeOrigin::signed(multisig_id),
+				multisig_id,
+				Some(Sunset { sunset_at: 10, beneficiary: None })
+			));
+		}
+		// The multisig checked first allows more pending transactions, weighing its wind down
+		let multisigs: Vec<u64> = Sunsets::<Test>::iter_keys().collect();
+		let (heavy, light) = (multisigs[0], multisigs[1]);
+		assert_ok!(Multisig::fund_multisig(RuntimeOrigin::signed(creator), heavy, 1_000));
+		assert_ok!(Multisig::set_max_pending_transactions(
+			RuntimeOrigin::signed(heavy),
+			heavy,
+			DEFAULT_MAX_PENDING_TRANSACTIONS * 2
+		));
+		let wind_down = Multisig::dissolve_weight(&light);
+		assert!(Multisig::dissolve_weight(&heavy).any_gt(wind_down));
+		System::set_block_number(10);
+		// The heavy multisig does not hold back the light one after it
+		Multisig::on_idle(10, wind_down);
+		assert!(Multisigs::<Test>::contains_key(&heavy));
+		assert!(!Multisigs::<Test>::contains_key(&light));
+		assert!(SunsetCursor::<Test>::get().is_none());
+		// It is wound down once an idle block has the weight for it
+		Multisig::on_idle(10, Weight::MAX);
+		assert!(!Multisigs::<Test>::contains_key(&heavy));
+	});
+}
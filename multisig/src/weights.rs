@@ -0,0 +1,137 @@
+//! Weights for pallet-multisig
+//!
+//! No `SubstrateWeight` is generated yet, so only the placeholder weights of `()` are available.
+//! The weight of `submit_transaction` leaves out the call it dispatches.
+//! Their database accesses are counted from the storage each call touches, while the computation
+//! times and proof sizes are rough estimates, only fit for tests and development. Generate the
+//! real weights by running the benchmarks on reference hardware with the `runtime-benchmarks`
+//! feature enabled:
+//!
+//! ```sh
+//! frame-omni-bencher v1 benchmark pallet \
+//! 	--runtime target/release/wbuild/pba-runtime/pba_runtime.wasm \
+//! 	--pallet pallet_multisig --extrinsic "*" \
+//! 	--output multisig/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_multisig.
+pub trait WeightInfo {
+	fn propose_transaction(m: u32, ) -> Weight;
+	fn vote(m: u32, ) -> Weight;
+	fn submit_transaction(m: u32, ) -> Weight;
+	fn delete_multisig(p: u32, m: u32, c: u32, e: u32, ) -> Weight;
+}
+
+// Placeholder weights for tests and development, until the benchmarks are run.
+impl WeightInfo for () {
+	/// Storage: `Multisig::Multisigs` (r:1 w:0)
+	/// Storage: `Multisig::Sunsets` (r:1 w:0)
+	/// Storage: `Multisig::Transactions` (r:1 w:1)
+	/// Storage: `Multisig::PendingTransactionCount` (r:1 w:1)
+	/// Storage: `Multisig::MaxPendingTransactions` (r:1 w:0)
+	/// Storage: `Multisig::TransactionNonce` (r:1 w:1)
+	/// Storage: `Multisig::TransactionCalls` (r:0 w:1)
+	/// Storage: `Multisig::CallLocations` (r:0 w:1)
+	/// Storage: `Multisig::History` (r:1 w:1)
+	/// The range of component `m` is `[1, MaxMembers]`.
+	fn propose_transaction(m: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(Weight::from_parts(400_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Multisig::Members` (r:1 w:0)
+	/// Storage: `Multisig::Transactions` (r:1 w:1)
+	/// Storage: `Multisig::History` (r:1 w:1)
+	/// Storage: `Multisig::BlockVoteCounts` (r:1 w:1)
+	/// Storage: `Multisig::AggregatedTransactionCount` (r:1 w:1)
+	/// The range of component `m` is `[2, MaxMembers]`.
+	fn vote(m: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 7_000)
+			.saturating_add(Weight::from_parts(300_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Multisig::Members` (r:1 w:0)
+	/// Storage: `Multisig::Transactions` (r:1 w:1)
+	/// Storage: `Multisig::TransactionCalls` (r:1 w:1)
+	/// Storage: `Multisig::RevealedAt` (r:1 w:1)
+	/// Storage: `Multisig::PendingTransactionCount` (r:1 w:1)
+	/// Storage: `Multisig::CallLocations` (r:0 w:1)
+	/// Storage: `Multisig::TransactionCategories` (r:1 w:1)
+	/// Storage: `Multisig::History` (r:1 w:1)
+	/// The range of component `m` is `[1, MaxMembers]`.
+	fn submit_transaction(m: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 9_000)
+			.saturating_add(Weight::from_parts(300_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `Multisig::Earmarks` (r:1 w:0)
+	/// Storage: `Multisig::EarmarkCount` (r:0 w:1)
+	/// Storage: `Multisig::Contributions` (r:1 w:0)
+	/// Storage: `Multisig::ContributorCount` (r:0 w:1)
+	/// Storage: `Multisig::Transactions` (r:1 w:0)
+	/// Storage: `Multisig::Signals` (r:1 w:0)
+	/// Storage: `Multisig::MultisigsByCreator` (r:1 w:1)
+	/// Storage: `Multisig::History` (r:0 w:1)
+	/// Storage: `Multisig::MinFundingAmounts` (r:0 w:1)
+	/// Storage: `Multisig::MaxPendingTransactions` (r:0 w:1)
+	/// Storage: `Multisig::PendingTransactionCount` (r:0 w:1)
+	/// Storage: `Multisig::TransactionNonce` (r:0 w:1)
+	/// Storage: `Multisig::Sunsets` (r:0 w:1)
+	/// Per pending transaction or signal `p`:
+	/// Storage: `Multisig::Transactions` (r:1 w:1)
+	/// Storage: `Multisig::TransactionCalls` (r:0 w:1)
+	/// Storage: `Multisig::CallLocations` (r:0 w:1)
+	/// Storage: `Multisig::TransactionCategories` (r:0 w:1)
+	/// Storage: `Multisig::RevealedAt` (r:0 w:1)
+	/// Per member `m`:
+	/// Storage: `Multisig::Members` (r:0 w:1)
+	/// Storage: `Multisig::VoteDelegates` (r:0 w:1)
+	/// Storage: `Multisig::LastSeen` (r:0 w:1)
+	/// Storage: `Multisig::MemberLabels` (r:0 w:1)
+	/// Per contributor `c`:
+	/// Storage: `Multisig::Contributions` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Per earmark `e`:
+	/// Storage: `Multisig::Earmarks` (r:1 w:1)
+	/// The range of component `p` is `[0, MaxPendingTransactionsLimit]`.
+	/// The range of component `m` is `[1, MaxMembers]`.
+	/// The range of component `c` is `[0, MaxContributors]`.
+	/// The range of component `e` is `[0, MaxEarmarks]`.
+	fn delete_multisig(p: u32, m: u32, c: u32, e: u32, ) -> Weight {
+		Weight::from_parts(120_000_000, 6_000)
+			.saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+}
@@ -0,0 +1,173 @@
+//! # Multisig Primitives
+//! The public types of the multisig pallet.
+//!
+//! ## Overview
+//!
+//! The types stored and emitted by the multisig pallet, kept apart from it so indexers,
+//! off-chain workers and front-end codegen can decode them without depending on FRAME. The
+//! variant indices of every enum are pinned, so their SCALE encodings stay stable as variants
+//! are added.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bounded_collections::{BoundedBTreeMap, BoundedBTreeSet, BoundedVec, Get};
+use codec::{Decode, Encode, MaxEncodedLen};
+use derive_where::derive_where;
+use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sp_arithmetic::{PerThing, Percent};
+
+#[cfg(test)]
+mod tests;
+
+/// Voting options on a proposed transaction.
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Vote {
+	#[codec(index = 0)]
+	Approve,
+	#[codec(index = 1)]
+	Reject,
+}
+
+/// The number of votes needed for a transaction to be approved or rejected.
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Threshold {
+	/// A fixed number of votes.
+	#[codec(index = 0)]
+	Absolute(u32),
+	/// A percentage of the combined vote weight of the members, rounded up.
+	#[codec(index = 1)]
+	Percentage(Percent),
+}
+
+impl Default for Threshold {
+	fn default() -> Self {
+		Threshold::Absolute(0)
+	}
+}
+
+impl From<u32> for Threshold {
+	fn from(votes: u32) -> Self {
+		Threshold::Absolute(votes)
+	}
+}
+
+impl Threshold {
+	/// The vote weight needed out of the combined `total_weight` of the members.
+	pub fn votes_required(&self, total_weight: u32) -> u32 {
+		match self {
+			Threshold::Absolute(votes) => *votes,
+			Threshold::Percentage(percent) => percent.mul_ceil(total_weight),
+		}
+	}
+}
+
+/// Potential statuses a transaction can have.
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionStatus {
+	#[default]
+	#[codec(index = 0)]
+	Pending,
+	#[codec(index = 1)]
+	Complete,
+	#[codec(index = 2)]
+	Canceled,
+	#[codec(index = 3)]
+	Rejected,
+	#[codec(index = 4)]
+	Expired,
+}
+
+/// The weighted votes cast on a transaction.
+///
+/// Encoded as the two counters in order, the same as when they were fields of the transaction.
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tally {
+	/// The weight of the approvals.
+	pub approvals: u32,
+	/// The weight of the rejections.
+	pub rejections: u32,
+}
+
+/// A multisig account along with its members.
+// `MaxMembers` is only a bound, so the derives are bounded on the stored types instead.
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[derive_where(Debug, PartialEq, Eq; AccountId, MaxMembers: Get<u32>, BlockNumber)]
+#[derive_where(Default; AccountId: Default + Ord, MaxMembers: Get<u32>, BlockNumber)]
+#[cfg_attr(
+	feature = "serde",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "AccountId: Serialize, BlockNumber: Serialize",
+		deserialize = "AccountId: Deserialize<'de> + Ord, MaxMembers: Get<u32> + Clone, \
+			BlockNumber: Deserialize<'de>"
+	))
+)]
+#[scale_info(skip_type_params(MaxMembers))]
+pub struct MultisigAccount<AccountId, MaxMembers, BlockNumber> {
+	/// The creator of the multisig.
+	pub creator: AccountId,
+	/// The members of the multisig.
+	pub members: BoundedBTreeSet<AccountId, MaxMembers>,
+	/// The number of votes required to approve or reject a transaction.
+	pub threshold: Threshold,
+	/// The block number at which the multisig was created.
+	pub created_at: BlockNumber,
+}
+
+/// A transaction proposed on a multisig.
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[derive_where(
+	Debug, PartialEq, Eq;
+	AccountId, MaxMembers: Get<u32>, MaxAttachments: Get<u32>, BlockNumber
+)]
+#[derive_where(
+	Default;
+	AccountId: Default + Ord, MaxMembers: Get<u32>, MaxAttachments: Get<u32>, BlockNumber
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(Serialize, Deserialize),
+	serde(bound(
+		serialize = "AccountId: Serialize, BlockNumber: Serialize",
+		deserialize = "AccountId: Deserialize<'de> + Ord, MaxMembers: Get<u32> + Clone, \
+			MaxAttachments: Get<u32>, BlockNumber: Deserialize<'de>"
+	))
+)]
+#[scale_info(skip_type_params(MaxMembers, MaxAttachments))]
+pub struct Transaction<AccountId, MaxMembers, MaxAttachments, BlockNumber> {
+	/// The proposer of the transaction.
+	pub proposer: AccountId,
+	/// The status of the transaction.
+	pub status: TransactionStatus,
+	/// The hash of the call, stored separately by the pallet.
+	pub call_hash: [u8; 32],
+	/// The votes cast on the transaction, each along with the weight of the voter when it was
+	/// cast.
+	pub votes: BoundedBTreeMap<AccountId, (Vote, u32), MaxMembers>,
+	/// The weighted tally of `votes`, updated as votes are cast.
+	pub tally: Tally,
+	/// The block number at which the transaction was created.
+	pub created_at: BlockNumber,
+	/// The block number at which the transaction was approved.
+	pub expires_at: BlockNumber,
+	/// The block number at which the transaction reached its approval threshold.
+	pub approved_at: Option<BlockNumber>,
+	/// The per multisig nonce of the transaction.
+	pub nonce: u64,
+	/// The threshold of the multisig at the time the transaction was proposed.
+	pub threshold: Threshold,
+	/// The combined vote weight of the members at the time the transaction was proposed.
+	pub total_weight: u32,
+	/// The content hashes of the off-chain documents attached to the transaction.
+	pub attachments: BoundedVec<[u8; 32], MaxAttachments>,
+	/// The block number before which the transaction can not be executed.
+	pub execute_not_before: Option<BlockNumber>,
+	/// The block number after which the transaction can no longer be executed.
+	pub execute_not_after: Option<BlockNumber>,
+}
@@ -0,0 +1,35 @@
+use crate::*;
+use codec::Encode;
+use sp_arithmetic::Percent;
+
+#[test]
+fn enum_encodings_are_stable() {
+	assert_eq!(Vote::Approve.encode(), vec![0]);
+	assert_eq!(Vote::Reject.encode(), vec![1]);
+	assert_eq!(TransactionStatus::Pending.encode(), vec![0]);
+	assert_eq!(TransactionStatus::Expired.encode(), vec![4]);
+	assert_eq!(Threshold::Absolute(2).encode(), vec![0, 2, 0, 0, 0]);
+	assert_eq!(Threshold::Percentage(Percent::from_percent(50)).encode(), vec![1, 50]);
+}
+
+#[test]
+fn tally_encodes_as_its_counters() {
+	let tally = Tally { approvals: 3, rejections: 1 };
+	assert_eq!(tally.encode(), (3u32, 1u32).encode());
+}
+
+#[test]
+fn votes_required_rounds_percentages_up() {
+	assert_eq!(Threshold::Absolute(2).votes_required(5), 2);
+	assert_eq!(Threshold::Percentage(Percent::from_percent(50)).votes_required(3), 2);
+}
+
+#[test]
+fn default_transaction_is_pending_without_votes() {
+	type MaxVotes = bounded_collections::ConstU32<3>;
+	let transaction = Transaction::<u64, MaxVotes, MaxVotes, u64>::default();
+	assert_eq!(transaction.status, TransactionStatus::Pending);
+	assert!(transaction.votes.is_empty());
+	assert_eq!(transaction.tally, Tally::default());
+	assert_eq!(transaction.approved_at, None);
+}
@@ -2,15 +2,17 @@
 
 extern crate alloc;
 
-use alloc::{vec, vec::Vec};
+use alloc::{boxed::Box, vec, vec::Vec};
 use frame::{
 	deps::{
 		codec::Compact,
 		frame_support::{
+			dispatch::GetDispatchInfo,
 			genesis_builder_helper::{build_state, get_preset},
 			runtime,
 			traits::AsEnsureOriginWithArg,
 			weights::FixedFee,
+			PalletId,
 		},
 	},
 	prelude::*,
@@ -167,10 +169,33 @@ impl pallet_multisig::Config for Runtime {
 	type NativeBalance = Balances;
 	type RuntimeCall = RuntimeCall;
 	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeTask = RuntimeTask;
 	type MaxMembers = ConstU32<10>;
 	type DefaultThreshold = ConstU32<6>;
 	type DefaultExpirationBlocks = ConstU32<100>;
 	type MultisigDeposit = ConstU128<10>;
+	type MaxMultisigsPerCreator = ConstU32<10>;
+	type MinFundingAmount = ConstU128<100>;
+	type PalletId = MultisigPalletId;
+	type OffchainSignature = sp_runtime::MultiSignature;
+	type OffchainPublic = sp_runtime::MultiSigner;
+	type VoteWeightProvider = ();
+	type MaxEarmarkPurposeLength = ConstU32<32>;
+	type MaxAttachments = ConstU32<8>;
+	type MaxSignalLength = ConstU32<256>;
+	type AggregateVoteEvents = ConstBool<false>;
+	type MaxAggregatedTransactions = ConstU32<64>;
+	type MaxHistoryLength = ConstU32<100>;
+	type MaxBatchCreate = ConstU32<10>;
+	type RevealPeriod = ConstU32<10>;
+	type MaxLabelLength = ConstU32<32>;
+	type DefaultMaxPendingTransactions = ConstU32<16>;
+	type MaxPendingTransactionsLimit = ConstU32<64>;
+	type PendingTransactionDeposit = ConstU128<10>;
+	type MaxContributors = ConstU32<100>;
+	type MaxEarmarks = ConstU32<16>;
+	// The placeholder weights, until the pallet benchmarks are run on reference hardware
+	type WeightInfo = ();
 }
 
 parameter_types! {
@@ -179,6 +204,9 @@ parameter_types! {
 	pub const DefaultThreshold: u32 = 6;
 	pub const DefaultExpirationBlocks: u32 = 100;
 	pub const MultisigDeposit: u128 = 10;
+	pub const MaxMultisigsPerCreator: u32 = 10;
+	pub const MinFundingAmount: u128 = 100;
+	pub const MultisigPalletId: PalletId = PalletId(*b"py/mltsg");
 }
 
 pub struct BlockAuthor;
@@ -222,8 +250,24 @@ type Nonce = <Runtime as frame_system::Config>::Nonce;
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
 type Header = HeaderFor<Runtime>;
 
-type RuntimeExecutive =
-	Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+/// Migrations to apply on runtime upgrade.
+type Migrations = (pallet_multisig::migrations::MigrateV0ToV1<Runtime>,);
+
+type RuntimeExecutive = Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	Migrations,
+>;
+
+#[cfg(feature = "runtime-benchmarks")]
+frame::deps::frame_benchmarking::define_benchmarks!(
+	[frame_benchmarking, BaselineBench::<Runtime>]
+	[frame_system, SystemBench::<Runtime>]
+	[pallet_multisig, Multisig]
+);
 
 impl_runtime_apis! {
 	impl apis::Core<Block> for Runtime {
@@ -350,6 +394,45 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_multisig::runtime_api::MultisigApi<
+		Block,
+		AccountId,
+		Balance,
+		RuntimeCall,
+		<Runtime as frame_system::Config>::Hash,
+		BlockNumberFor<Runtime>,
+	> for Runtime {
+		fn estimate_lifecycle_fees(multisig: AccountId, call: RuntimeCall) -> Option<Balance> {
+			// The weight of the call is priced apart, the declared weight of `submit_transaction`
+			// leaving out the call it dispatches
+			let execution = TransactionPayment::weight_to_fee(call.get_dispatch_info().call_weight);
+			let calls = Multisig::lifecycle_calls(multisig, Box::new(call))?;
+			Some(calls.into_iter().fold(execution, |total: Balance, call| {
+				let call = RuntimeCall::Multisig(call);
+				let len = call.encoded_size() as u32;
+				total.saturating_add(TransactionPayment::query_call_info(call, len).partial_fee)
+			}))
+		}
+
+		fn activity(
+			multisig: AccountId,
+			since_block: BlockNumberFor<Runtime>,
+		) -> Vec<pallet_multisig::ActivityEntryOf<Runtime>> {
+			Multisig::activity(&multisig, since_block)
+		}
+
+		fn members(multisig: AccountId) -> Vec<(AccountId, Option<Vec<u8>>)> {
+			Multisig::members(&multisig)
+				.into_iter()
+				.map(|(member, label)| (member, label.map(Into::into)))
+				.collect()
+		}
+
+		fn liveness(multisig: AccountId) -> Vec<(AccountId, Option<BlockNumberFor<Runtime>>)> {
+			Multisig::liveness(&multisig)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame::deps::frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (